@@ -0,0 +1,35 @@
+// Detect the compiler version, so that newer standard library APIs can be used
+// when available while keeping the minimum supported Rust version.
+
+use std::env;
+use std::process::Command;
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rustc-check-cfg=cfg(has_repeat_n)");
+    println!("cargo:rustc-check-cfg=cfg(has_thread_scope)");
+    let minor = match rustc_minor_version() {
+        Some(minor) => minor,
+        None => return,
+    };
+    // `std::thread::scope` was stabilized in Rust 1.63.
+    if minor >= 63 {
+        println!("cargo:rustc-cfg=has_thread_scope");
+    }
+    // `core::iter::repeat_n` was stabilized in Rust 1.82.
+    if minor >= 82 {
+        println!("cargo:rustc-cfg=has_repeat_n");
+    }
+}
+
+fn rustc_minor_version() -> Option<u32> {
+    let rustc = env::var_os("RUSTC")?;
+    let output = Command::new(rustc).arg("--version").output().ok()?;
+    let version = String::from_utf8(output.stdout).ok()?;
+    // The output has the form "rustc 1.63.0 (4b91a6ea7 2022-08-08)".
+    let mut parts = version.split_whitespace().nth(1)?.split('.');
+    if parts.next()? != "1" {
+        return None;
+    }
+    parts.next()?.parse().ok()
+}
@@ -0,0 +1,31 @@
+//! Apply random sequences of operations to a `zip_clone` iterator and compare
+//! the results with a reference implementation.
+//!
+//! Run with `cargo +nightly fuzz run differential` from the repository root.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use zip_clone::testing::{check_steps, Step};
+
+fuzz_target!(|data: &[u8]| {
+    let mut bytes = data.iter().copied();
+    let len = match bytes.next() {
+        Some(len) => u32::from(len % 32),
+        None => return,
+    };
+    let mut steps = Vec::new();
+    while let Some(op) = bytes.next() {
+        let mut arg = || bytes.next().unwrap_or(0);
+        steps.push(match op % 7 {
+            0 => Step::Next,
+            1 => Step::NextBack,
+            2 => Step::Nth(usize::from(arg() % 8)),
+            3 => Step::NthBack(usize::from(arg() % 8)),
+            4 => Step::Find(u32::from(arg() % 32)),
+            5 => Step::Fold,
+            _ => Step::Drop,
+        });
+    }
+    check_steps(len, &steps);
+});
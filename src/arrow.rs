@@ -0,0 +1,168 @@
+//! Fill [Arrow](::arrow_array) array builders with copies of a value.
+//!
+//! The builders' own `Extend` implementations consume owned values, so
+//! filling a builder from a repeated `Vec` or `String` otherwise needs a clone
+//! for every row. These helpers use `n - 1` clones, with the last row
+//! receiving the original value, and reserve space up front where the
+//! builder allows it.
+
+use ::arrow_array::builder::{ArrayBuilder, GenericByteBuilder, GenericListBuilder};
+use ::arrow_array::types::ByteArrayType;
+use ::arrow_array::OffsetSizeTrait;
+
+use crate::repeat_clones;
+
+/// Append `n` copies of a value to a builder.
+///
+/// This works with any builder that can be extended with optional values,
+/// including nested builders such as [`ListBuilder`](::arrow_array::builder::ListBuilder),
+/// which take each list as an owned iterator.
+///
+/// Example:
+/// ```rust
+/// use arrow_array::builder::{Int32Builder, ListBuilder};
+/// use arrow_array::Array;
+/// use zip_clone::arrow::append_clones;
+///
+/// let mut builder = ListBuilder::new(Int32Builder::new());
+/// append_clones(&mut builder, vec![Some(1), None, Some(3)], 4);
+/// let array = builder.finish();
+/// assert_eq!(array.len(), 4);
+/// assert_eq!(array.values().len(), 12);
+/// ```
+pub fn append_clones<B, V>(builder: &mut B, value: V, n: usize)
+where
+    B: Extend<Option<V>>,
+    V: Clone,
+{
+    builder.extend(repeat_clones(value, n).map(Some));
+}
+
+/// Create a string or binary builder holding `n` copies of a value.
+///
+/// The builder's offset and data buffers are allocated at their final size
+/// before any rows are appended. The value is only borrowed, so it is never
+/// cloned.
+///
+/// **Panics** if the total data length overflows `usize`.
+///
+/// Example:
+/// ```rust
+/// use arrow_array::types::Utf8Type;
+/// use arrow_array::Array;
+/// use zip_clone::arrow::byte_builder_of_clones;
+///
+/// let mut builder = byte_builder_of_clones::<Utf8Type, _>("Hello", 3);
+/// let array = builder.finish();
+/// assert_eq!(array.len(), 3);
+/// assert_eq!(array.value(2), "Hello");
+/// ```
+pub fn byte_builder_of_clones<T, V>(value: V, n: usize) -> GenericByteBuilder<T>
+where
+    T: ByteArrayType,
+    V: AsRef<T::Native>,
+{
+    let bytes: &[u8] = value.as_ref().as_ref();
+    let data_len = bytes.len().checked_mul(n).expect("data length overflow");
+    let mut builder = GenericByteBuilder::with_capacity(n, data_len);
+    for _ in 0..n {
+        builder.append_value(&value);
+    }
+    builder
+}
+
+/// Create a list builder holding `n` copies of a list.
+///
+/// The list offsets are allocated for `n` rows before any rows are appended.
+/// The values builder is used as given, so create it with
+/// `with_capacity` to reserve space for the list elements as well.
+///
+/// Example:
+/// ```rust
+/// use arrow_array::builder::StringBuilder;
+/// use arrow_array::Array;
+/// use zip_clone::arrow::list_builder_of_clones;
+///
+/// let values = StringBuilder::with_capacity(4, 16);
+/// let list = vec![Some("a"), Some("b")];
+/// let mut builder = list_builder_of_clones::<i32, _, _, _>(values, list, 2);
+/// let array = builder.finish();
+/// assert_eq!(array.len(), 2);
+/// assert_eq!(array.values().len(), 4);
+/// ```
+pub fn list_builder_of_clones<O, B, V, E>(
+    values_builder: B,
+    value: V,
+    n: usize,
+) -> GenericListBuilder<O, B>
+where
+    O: OffsetSizeTrait,
+    B: ArrayBuilder + Extend<E>,
+    V: IntoIterator<Item = E> + Clone,
+{
+    let mut builder = GenericListBuilder::with_capacity(values_builder, n);
+    append_clones(&mut builder, value, n);
+    builder
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use ::arrow_array::builder::{Int32Builder, ListBuilder, StringBuilder};
+    use ::arrow_array::types::BinaryType;
+    use ::arrow_array::Array;
+
+    use super::{append_clones, byte_builder_of_clones, list_builder_of_clones};
+    use crate::tests::Clonable;
+
+    // A list of one item that counts its clones.
+    #[derive(Clone)]
+    struct CountedList<'a> {
+        _clonable: Clonable<'a>,
+    }
+
+    impl IntoIterator for CountedList<'_> {
+        type Item = Option<i32>;
+        type IntoIter = std::iter::Once<Option<i32>>;
+
+        fn into_iter(self) -> Self::IntoIter {
+            std::iter::once(Some(1))
+        }
+    }
+
+    #[test]
+    fn test_list_builder_of_clones() {
+        let count = AtomicU32::new(0);
+        let list = CountedList {
+            _clonable: Clonable { count: &count },
+        };
+        let mut builder = list_builder_of_clones::<i32, _, _, _>(Int32Builder::new(), list, 5);
+        assert_eq!(builder.finish().len(), 5);
+        assert_eq!(count.load(Ordering::Relaxed), 4);
+    }
+
+    #[test]
+    fn test_append_clones_empty() {
+        let count = AtomicU32::new(0);
+        let list = CountedList {
+            _clonable: Clonable { count: &count },
+        };
+        let mut builder = ListBuilder::new(Int32Builder::new());
+        append_clones(&mut builder, list, 0);
+        assert_eq!(builder.finish().len(), 0);
+        assert_eq!(count.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn test_byte_builder_of_clones() {
+        let mut builder = byte_builder_of_clones::<BinaryType, _>(b"abc", 4);
+        assert_eq!(builder.values_slice().len(), 12);
+        let array = builder.finish();
+        assert_eq!(array.len(), 4);
+        assert_eq!(array.value(3), b"abc");
+        let mut strings = StringBuilder::new();
+        append_clones(&mut strings, String::from("x"), 2);
+        assert_eq!(strings.finish().value(1), "x");
+    }
+}
@@ -0,0 +1,208 @@
+use core::iter::{FusedIterator, Peekable};
+use std::sync::mpsc::{sync_channel, Receiver};
+use std::sync::Arc;
+
+/// Zip an iterator to a value that is cloned on a background thread.
+///
+/// While each item is being processed, a background thread prepares clones
+/// for the following items, hiding the latency of expensive clones. Up to
+/// `queue_len` clones are buffered ahead of use, plus one clone in progress.
+///
+/// The background thread is only started when a clone is first needed. If it
+/// was never started, the last item receives the original value. Otherwise
+/// the last item receives a clone made by the background thread, and clones
+/// made ahead of use are discarded, so this uses up to `queue_len + 2` more
+/// clones than [`zip_clone`](crate::zip_clone). The iterator does not wait
+/// for the background thread to exit, so reaching the end or dropping the
+/// iterator does not block on a clone that will be discarded.
+///
+/// Example:
+/// ```rust
+/// use zip_clone::zip_clone_background;
+///
+/// let v = vec![0u8; 1 << 20];
+/// for (i, v) in zip_clone_background(0..10, v, 2) {
+///     assert_eq!(v.len(), 1 << 20);
+/// }
+/// ```
+pub fn zip_clone_background<I, C>(iter: I, value: C, queue_len: usize) -> ZipCloneBackground<I, C>
+where
+    I: Iterator,
+    C: Clone + Send + Sync + 'static,
+{
+    ZipCloneBackground {
+        iter: iter.peekable(),
+        original: Some(Arc::new(value)),
+        worker: None,
+        queue_len,
+    }
+}
+
+/// An iterator that pairs items with values cloned on a background thread.
+///
+/// This struct is created by [`zip_clone_background`].
+pub struct ZipCloneBackground<I, C>
+where
+    I: Iterator,
+{
+    iter: Peekable<I>,
+    original: Option<Arc<C>>,
+    worker: Option<Worker<C>>,
+    queue_len: usize,
+}
+
+// Dropping a worker drops its receiver, so that the background thread exits
+// once its current clone is finished. The thread is not joined.
+struct Worker<C> {
+    receiver: Receiver<C>,
+}
+
+impl<C> Worker<C>
+where
+    C: Clone + Send + Sync + 'static,
+{
+    fn start(value: Arc<C>, queue_len: usize) -> Self {
+        let (sender, receiver) = sync_channel(queue_len);
+        std::thread::spawn(move || {
+            // Keep cloning until the receiver is dropped.
+            while sender.send((*value).clone()).is_ok() {}
+        });
+        Worker { receiver }
+    }
+}
+
+impl<I, C> Iterator for ZipCloneBackground<I, C>
+where
+    I: Iterator,
+    C: Clone + Send + Sync + 'static,
+{
+    type Item = (I::Item, C);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.original.as_ref()?;
+        let item = match self.iter.next() {
+            Some(item) => item,
+            None => {
+                self.worker = None;
+                self.original = None;
+                return None;
+            }
+        };
+        if self.iter.peek().is_none() {
+            let original = self.original.take()?;
+            // A running worker holds a reference to the original, so use a
+            // clone it has made rather than waiting for it to exit.
+            let worker = self.worker.take();
+            let value = match worker.and_then(|worker| worker.receiver.recv().ok()) {
+                Some(cloned) => cloned,
+                None => Arc::try_unwrap(original).unwrap_or_else(|shared| (*shared).clone()),
+            };
+            return Some((item, value));
+        }
+        let original = self.original.as_ref()?;
+        let queue_len = self.queue_len;
+        let worker = self
+            .worker
+            .get_or_insert_with(|| Worker::start(original.clone(), queue_len));
+        // If the worker has stopped, its clone panicked. Cloning here will
+        // most likely propagate the same panic.
+        let cloned = worker
+            .receiver
+            .recv()
+            .unwrap_or_else(|_| (**original).clone());
+        Some((item, cloned))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.original.is_some() {
+            self.iter.size_hint()
+        } else {
+            (0, Some(0))
+        }
+    }
+}
+
+impl<I, C> ExactSizeIterator for ZipCloneBackground<I, C>
+where
+    I: ExactSizeIterator,
+    C: Clone + Send + Sync + 'static,
+{
+}
+
+impl<I, C> FusedIterator for ZipCloneBackground<I, C>
+where
+    I: Iterator,
+    C: Clone + Send + Sync + 'static,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    use crate::zip_clone_background;
+
+    struct Counted(Arc<AtomicU32>);
+
+    impl Clone for Counted {
+        fn clone(&self) -> Self {
+            self.0.fetch_add(1, Ordering::Relaxed);
+            Counted(self.0.clone())
+        }
+    }
+
+    // A value whose clones after the first wait for a gate to open.
+    struct Gated {
+        clones: Arc<AtomicU32>,
+        gate: Arc<Mutex<()>>,
+    }
+
+    impl Clone for Gated {
+        fn clone(&self) -> Self {
+            if self.clones.fetch_add(1, Ordering::Relaxed) > 0 {
+                let _open = self.gate.lock().unwrap();
+            }
+            Gated {
+                clones: self.clones.clone(),
+                gate: self.gate.clone(),
+            }
+        }
+    }
+
+    #[test]
+    fn test_zip_clone_background() {
+        let count = Arc::new(AtomicU32::new(0));
+        let cloned = Counted(count.clone());
+        assert_eq!(
+            zip_clone_background(1..11, cloned, 2).map(|_| ()).count(),
+            10
+        );
+        let count = count.load(Ordering::Relaxed);
+        assert!((10..=13).contains(&count), "{}", count);
+    }
+
+    #[test]
+    fn test_zip_clone_background_single() {
+        let count = Arc::new(AtomicU32::new(0));
+        let cloned = Counted(count.clone());
+        assert_eq!(zip_clone_background(1..2, cloned, 2).map(|_| ()).count(), 1);
+        assert_eq!(count.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn test_zip_clone_background_drop() {
+        let gate = Arc::new(Mutex::new(()));
+        let closed = gate.lock().unwrap();
+        let value = Gated {
+            clones: Arc::new(AtomicU32::new(0)),
+            gate: gate.clone(),
+        };
+        let mut iter = zip_clone_background(0..3, value, 0);
+        assert!(iter.next().is_some());
+        // The worker is blocked making the second clone, so waiting for it
+        // here would deadlock.
+        drop(iter);
+        drop(closed);
+    }
+}
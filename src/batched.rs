@@ -0,0 +1,174 @@
+use alloc::vec::Vec;
+use core::iter::{FusedIterator, Peekable};
+
+/// Zip an iterator to a value that is cloned in batches.
+///
+/// Instead of cloning the value as each item is returned, clones are made up
+/// to `batch_size` at a time and buffered. A batch never contains more clones
+/// than the items known to remain from the iterator's `size_hint`, so a fully
+/// consumed iterator still uses one fewer clones than items, with the original
+/// value returned with the last item.
+///
+/// Example:
+/// ```rust
+/// use zip_clone::zip_clone_batched;
+///
+/// let s = String::from("Hello");
+/// for (i, s) in zip_clone_batched(0..10, s, 4) {
+///     assert_eq!(s, String::from("Hello"));
+/// }
+/// ```
+pub fn zip_clone_batched<I, C>(iter: I, value: C, batch_size: usize) -> ZipCloneBatched<I, C>
+where
+    I: Iterator,
+    C: Clone,
+{
+    ZipCloneBatched {
+        iter: iter.peekable(),
+        original: Some(value),
+        spare: Vec::new(),
+        batch_size: batch_size.max(1),
+    }
+}
+
+/// An iterator that pairs items with values cloned in batches.
+///
+/// This struct is created by [`zip_clone_batched`].
+pub struct ZipCloneBatched<I, C>
+where
+    I: Iterator,
+{
+    iter: Peekable<I>,
+    original: Option<C>,
+    spare: Vec<C>,
+    batch_size: usize,
+}
+
+impl<I, C> ZipCloneBatched<I, C>
+where
+    I: Iterator,
+    C: Clone,
+{
+    // Pair an item taken from the iterator with a value.
+    fn pair(&mut self, item: Option<I::Item>) -> Option<(I::Item, C)> {
+        let original = self.original.as_ref()?;
+        let item = match item {
+            Some(item) => item,
+            None => {
+                self.original = None;
+                self.spare = Vec::new();
+                return None;
+            }
+        };
+        if self.iter.peek().is_none() {
+            self.spare = Vec::new();
+            return self.original.take().map(|original| (item, original));
+        }
+        if self.spare.is_empty() {
+            // This item needs a clone, as do all remaining items except the
+            // last. The remaining items include the peeked item.
+            let needed = self.iter.size_hint().0.max(1);
+            let batch = needed.min(self.batch_size);
+            self.spare
+                .extend(core::iter::repeat_with(|| original.clone()).take(batch));
+        }
+        self.spare.pop().map(|cloned| (item, cloned))
+    }
+}
+
+impl<I, C> Iterator for ZipCloneBatched<I, C>
+where
+    I: Iterator,
+    C: Clone,
+{
+    type Item = (I::Item, C);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.iter.next();
+        self.pair(item)
+    }
+
+    fn count(self) -> usize
+    where
+        Self: Sized,
+    {
+        if self.original.is_some() {
+            self.iter.count()
+        } else {
+            0
+        }
+    }
+
+    fn last(mut self) -> Option<Self::Item>
+    where
+        Self: Sized,
+    {
+        match (self.original.take(), self.iter.last()) {
+            (Some(original), Some(item)) => Some((item, original)),
+            _ => None,
+        }
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let item = self.iter.nth(n);
+        self.pair(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.original.is_some() {
+            self.iter.size_hint()
+        } else {
+            (0, Some(0))
+        }
+    }
+}
+
+impl<I, C> ExactSizeIterator for ZipCloneBatched<I, C>
+where
+    I: ExactSizeIterator,
+    C: Clone,
+{
+}
+
+impl<I, C> FusedIterator for ZipCloneBatched<I, C>
+where
+    I: Iterator,
+    C: Clone,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use crate::tests::Clonable;
+    use crate::zip_clone_batched;
+
+    #[test]
+    fn test_zip_clone_batched() {
+        let iter = 1..11;
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        assert_eq!(zip_clone_batched(iter, cloned, 4).map(|_| ()).count(), 10);
+        assert_eq!(count.load(Ordering::Relaxed), 9);
+    }
+
+    #[test]
+    fn test_zip_clone_batched_unknown_length() {
+        let iter = (1..11).filter(|_| true);
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        assert_eq!(zip_clone_batched(iter, cloned, 4).map(|_| ()).count(), 10);
+        assert_eq!(count.load(Ordering::Relaxed), 9);
+    }
+
+    #[test]
+    fn test_zip_clone_batched_size() {
+        let iter = 1..11;
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let mut iter = zip_clone_batched(iter, cloned, 4);
+        iter.next();
+        assert_eq!(count.load(Ordering::Relaxed), 4);
+    }
+}
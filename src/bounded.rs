@@ -0,0 +1,95 @@
+use crate::error::CloneBudgetExceeded;
+use crate::{zip_clone, zip_clone_exact, ZipCloneIter};
+
+/// A maximum number of clones, expressed in the type.
+///
+/// The methods zip a collection to clones of a value like
+/// [`zip_clone`](crate::zip_clone), but first check that doing so makes at
+/// most `MAX` clones. For arrays the length is known at compile time, so
+/// exceeding the budget fails to compile. For other iterators the length is
+/// only known at run time, and exceeding the budget returns an error.
+///
+/// Example:
+/// ```rust
+/// use zip_clone::CloneBudget;
+///
+/// let pairs = CloneBudget::<2>::zip_exact([1, 2, 3], String::from("Hello"));
+/// assert_eq!(pairs[2].1, "Hello");
+///
+/// assert!(CloneBudget::<2>::zip(0..3, String::from("Hello")).is_ok());
+/// assert!(CloneBudget::<2>::zip(0..4, String::from("Hello")).is_err());
+/// ```
+///
+/// An array that needs more clones than the budget is rejected by the
+/// compiler:
+/// ```compile_fail
+/// use zip_clone::CloneBudget;
+///
+/// let pairs = CloneBudget::<2>::zip_exact([1, 2, 3, 4], String::from("Hello"));
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CloneBudget<const MAX: usize>;
+
+// Evaluating `OK` fails to compile if an array of `N` items needs more than
+// `MAX` clones.
+struct Check<const N: usize, const MAX: usize>;
+
+impl<const N: usize, const MAX: usize> Check<N, MAX> {
+    const OK: () = assert!(
+        N.saturating_sub(1) <= MAX,
+        "zipping the array needs more clones than the budget"
+    );
+}
+
+impl<const MAX: usize> CloneBudget<MAX> {
+    /// Zip an array to clones of a value, failing to compile if this needs
+    /// more than `MAX` clones.
+    ///
+    /// This is [`zip_clone_exact`](crate::zip_clone_exact) with a compile
+    /// time check.
+    pub fn zip_exact<T, C, const N: usize>(items: [T; N], value: C) -> [(T, C); N]
+    where
+        C: Clone,
+    {
+        let () = Check::<N, MAX>::OK;
+        zip_clone_exact(items, value)
+    }
+
+    /// Zip an exact-size iterator to clones of a value, returning an error if
+    /// this needs more than `MAX` clones.
+    ///
+    /// The check uses the length reported by the iterator, before any item is
+    /// taken.
+    pub fn zip<I, C>(iter: I, value: C) -> Result<ZipCloneIter<I::IntoIter, C>, CloneBudgetExceeded>
+    where
+        I: IntoIterator,
+        I::IntoIter: ExactSizeIterator,
+        C: Clone,
+    {
+        let iter = iter.into_iter();
+        let clones = iter.len().saturating_sub(1);
+        if clones > MAX {
+            return Err(CloneBudgetExceeded::new(clones, MAX));
+        }
+        Ok(zip_clone(iter, value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use crate::tests::Clonable;
+    use crate::CloneBudget;
+
+    #[test]
+    fn test_clone_budget() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        assert_eq!(CloneBudget::<4>::zip_exact([0; 5], cloned.clone()).len(), 5);
+        let error = CloneBudget::<4>::zip(0..6, cloned).err().unwrap();
+        assert_eq!(error.clones(), 5);
+        assert_eq!(error.max(), 4);
+        assert_eq!(count.load(Ordering::Relaxed), 5);
+    }
+}
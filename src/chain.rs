@@ -0,0 +1,161 @@
+use core::iter::FusedIterator;
+
+/// An iterator that returns the items of one iterator followed by the items
+/// of another.
+///
+/// Unlike [`core::iter::Chain`], this implements `ExactSizeIterator` when both
+/// iterators do, so a [`ZipCloneIter`](crate::ZipCloneIter) created by
+/// [`ZipCloneIter::chain_items`](crate::ZipCloneIter::chain_items) keeps an
+/// exact length. The combined length must fit in a `usize`.
+///
+/// This struct is created by
+/// [`ZipCloneIter::chain_items`](crate::ZipCloneIter::chain_items).
+pub struct ChainItems<A, B>
+where
+    A: Iterator,
+{
+    // Set to `None` once exhausted, so that it is not polled again.
+    first: Option<A>,
+    // An item already taken from the back of `first`, returned after it.
+    middle: Option<A::Item>,
+    second: B,
+}
+
+impl<A, B> ChainItems<A, B>
+where
+    A: Iterator,
+{
+    pub(crate) fn with_middle(first: A, middle: Option<A::Item>, second: B) -> Self {
+        ChainItems {
+            first: Some(first),
+            middle,
+            second,
+        }
+    }
+}
+
+impl<A, B> Iterator for ChainItems<A, B>
+where
+    A: Iterator,
+    B: Iterator<Item = A::Item>,
+{
+    type Item = A::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(first) = &mut self.first {
+            match first.next() {
+                Some(item) => return Some(item),
+                None => self.first = None,
+            }
+        }
+        if let Some(item) = self.middle.take() {
+            return Some(item);
+        }
+        self.second.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.second.size_hint();
+        let middle = self.middle.is_some() as usize;
+        let (lower, upper) = (
+            lower.saturating_add(middle),
+            upper.and_then(|upper| upper.checked_add(middle)),
+        );
+        match &self.first {
+            Some(first) => {
+                let (first_lower, first_upper) = first.size_hint();
+                (
+                    lower.saturating_add(first_lower),
+                    upper
+                        .zip(first_upper)
+                        .and_then(|(upper, first_upper)| upper.checked_add(first_upper)),
+                )
+            }
+            None => (lower, upper),
+        }
+    }
+
+    fn count(self) -> usize {
+        self.first.map_or(0, Iterator::count) + self.middle.is_some() as usize + self.second.count()
+    }
+
+    fn last(self) -> Option<Self::Item> {
+        let last = self.first.and_then(Iterator::last);
+        let last = self.middle.or(last);
+        self.second.last().or(last)
+    }
+
+    fn fold<Acc, F>(self, init: Acc, mut f: F) -> Acc
+    where
+        F: FnMut(Acc, Self::Item) -> Acc,
+    {
+        let acc = match self.first {
+            Some(first) => first.fold(init, &mut f),
+            None => init,
+        };
+        let acc = match self.middle {
+            Some(item) => f(acc, item),
+            None => acc,
+        };
+        self.second.fold(acc, f)
+    }
+}
+
+impl<A, B> DoubleEndedIterator for ChainItems<A, B>
+where
+    A: DoubleEndedIterator,
+    B: DoubleEndedIterator<Item = A::Item>,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match self.second.next_back() {
+            Some(item) => Some(item),
+            None => match self.middle.take() {
+                Some(item) => Some(item),
+                None => self.first.as_mut()?.next_back(),
+            },
+        }
+    }
+}
+
+impl<A, B> ExactSizeIterator for ChainItems<A, B>
+where
+    A: ExactSizeIterator,
+    B: ExactSizeIterator<Item = A::Item>,
+{
+}
+
+impl<A, B> FusedIterator for ChainItems<A, B>
+where
+    A: Iterator,
+    B: FusedIterator<Item = A::Item>,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use crate::tests::Clonable;
+    use crate::zip_clone;
+
+    #[test]
+    fn test_chain_items() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let mut iter = zip_clone(1..3, cloned);
+        assert_eq!(iter.next().map(|(i, _)| i), Some(1));
+        let iter = iter.chain_items(3..6);
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.map(|(i, _)| i).collect::<Vec<_>>(), [2, 3, 4, 5]);
+        assert_eq!(count.load(Ordering::Relaxed), 4);
+    }
+
+    #[test]
+    fn test_chain_items_back() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let iter = zip_clone(1..3, cloned).chain_items(3..5).rev();
+        assert_eq!(iter.map(|(i, _)| i).collect::<Vec<_>>(), [4, 3, 2, 1]);
+        assert_eq!(count.load(Ordering::Relaxed), 3);
+    }
+}
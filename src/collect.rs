@@ -0,0 +1,71 @@
+use alloc::vec::Vec;
+
+use crate::Prototype;
+
+/// Map each item of a vector, together with a copy of a value, into a new
+/// vector that can reuse the allocation of the original vector.
+///
+/// Collecting `vec.into_iter().zip_clone(value).map(f)` always allocates a
+/// new vector, since the standard library only collects in place from a small
+/// set of adapters. This function maps the items using only those adapters,
+/// so when `U` has a compatible layout with `T`, such as the same size and
+/// alignment, the result can reuse the original allocation. Reuse is best
+/// effort: the standard library does not guarantee it.
+///
+/// The last item receives the original value, so `n` items use `n - 1` clones.
+///
+/// Example:
+/// ```rust
+/// use zip_clone::collect_reusing;
+///
+/// let v = vec![1u64, 2, 3];
+/// let offset = String::from("1000");
+/// let v = collect_reusing(v, offset, |item, offset| item + offset.parse::<u64>().unwrap());
+/// assert_eq!(v, [1001, 1002, 1003]);
+/// ```
+pub fn collect_reusing<T, C, U, F>(vec: Vec<T>, value: C, mut f: F) -> Vec<U>
+where
+    C: Clone,
+    F: FnMut(T, C) -> U,
+{
+    let mut remaining = vec.len();
+    let mut prototype = Some(Prototype::new(value));
+    vec.into_iter()
+        .map(move |item| {
+            remaining -= 1;
+            let value = if remaining == 0 {
+                prototype.take().map(Prototype::into_inner)
+            } else {
+                prototype.as_ref().map(Prototype::stamp)
+            };
+            f(item, value.expect("one value for each item"))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use crate::collect_reusing;
+    use crate::tests::Clonable;
+
+    #[test]
+    fn test_collect_reusing() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let v = vec![1usize, 2, 3, 4, 5];
+        let v = collect_reusing(v, cloned, |item, _| item as isize * 2);
+        assert_eq!(v, [2, 4, 6, 8, 10]);
+        assert_eq!(count.load(Ordering::Relaxed), 4);
+    }
+
+    #[test]
+    fn test_collect_reusing_empty() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let v = collect_reusing(Vec::<u32>::new(), cloned, |item, _| item);
+        assert!(v.is_empty());
+        assert_eq!(count.load(Ordering::Relaxed), 0);
+    }
+}
@@ -0,0 +1,232 @@
+use alloc::borrow::{Cow, ToOwned};
+use core::iter::FusedIterator;
+
+use crate::{zip_clone, ZipCloneIter};
+
+/// Zip an iterator to a copy-on-write value.
+///
+/// If the value is `Cow::Borrowed`, every item is paired with a borrow of the
+/// same data. No clones of the borrowed data are made and no lookahead is
+/// performed. Consumers that need to modify their value can call
+/// [`Cow::to_mut`], which materializes owned data only for that item.
+///
+/// If the value is `Cow::Owned`, this behaves like [`zip_clone`], using one
+/// fewer clones than items.
+///
+/// Example:
+/// ```rust
+/// use std::borrow::Cow;
+/// use zip_clone::zip_cow;
+///
+/// let s = String::from("Hello");
+/// for (i, s) in zip_cow(0..10, Cow::Borrowed(s.as_str())) {
+///     assert!(matches!(s, Cow::Borrowed("Hello")));
+/// }
+/// ```
+pub fn zip_cow<'a, I, B>(iter: I, cow: Cow<'a, B>) -> ZipCowIter<'a, I, B>
+where
+    I: Iterator,
+    B: ToOwned + ?Sized,
+{
+    let inner = match cow {
+        Cow::Borrowed(borrowed) => CowInner::Borrowed(iter, borrowed),
+        owned @ Cow::Owned(_) => CowInner::Owned(zip_clone(iter, owned)),
+    };
+    ZipCowIter { inner }
+}
+
+/// Zip an iterator to a static value, pairing every item with a borrow of it.
+///
+/// This is [`zip_cow`] with a `Cow::Borrowed` value. No clones are made, and
+/// each consumer can call [`Cow::to_mut`] to get an owned copy only if it
+/// needs to modify its value. This suits string literals and other static
+/// data.
+///
+/// Example:
+/// ```rust
+/// use std::borrow::Cow;
+/// use zip_clone::zip_static_clone;
+///
+/// for (i, mut s) in zip_static_clone(0..3, "Hello") {
+///     if i == 1 {
+///         s.to_mut().push('!');
+///     }
+///     assert!(matches!(s, Cow::Borrowed("Hello")) || i == 1);
+/// }
+/// ```
+pub fn zip_static_clone<I, B>(iter: I, value: &'static B) -> ZipCowIter<'static, I, B>
+where
+    I: Iterator,
+    B: ToOwned + ?Sized,
+{
+    zip_cow(iter, Cow::Borrowed(value))
+}
+
+/// An iterator that pairs items with a copy-on-write value.
+///
+/// This struct is created by [`zip_cow`].
+pub struct ZipCowIter<'a, I, B>
+where
+    I: Iterator,
+    B: ToOwned + ?Sized,
+{
+    inner: CowInner<'a, I, B>,
+}
+
+enum CowInner<'a, I, B>
+where
+    I: Iterator,
+    B: ToOwned + ?Sized,
+{
+    Borrowed(I, &'a B),
+    Owned(ZipCloneIter<I, Cow<'a, B>>),
+}
+
+impl<'a, I, B> Iterator for ZipCowIter<'a, I, B>
+where
+    I: Iterator,
+    B: ToOwned + ?Sized,
+{
+    type Item = (I::Item, Cow<'a, B>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.inner {
+            CowInner::Borrowed(iter, borrowed) => {
+                let borrowed = *borrowed;
+                iter.next().map(|item| (item, Cow::Borrowed(borrowed)))
+            }
+            CowInner::Owned(iter) => iter.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match &self.inner {
+            CowInner::Borrowed(iter, _) => iter.size_hint(),
+            CowInner::Owned(iter) => iter.size_hint(),
+        }
+    }
+
+    fn count(self) -> usize
+    where
+        Self: Sized,
+    {
+        match self.inner {
+            CowInner::Borrowed(iter, _) => iter.count(),
+            CowInner::Owned(iter) => iter.count(),
+        }
+    }
+
+    fn last(self) -> Option<Self::Item>
+    where
+        Self: Sized,
+    {
+        match self.inner {
+            CowInner::Borrowed(iter, borrowed) => {
+                iter.last().map(|item| (item, Cow::Borrowed(borrowed)))
+            }
+            CowInner::Owned(iter) => iter.last(),
+        }
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        match &mut self.inner {
+            CowInner::Borrowed(iter, borrowed) => {
+                let borrowed = *borrowed;
+                iter.nth(n).map(|item| (item, Cow::Borrowed(borrowed)))
+            }
+            CowInner::Owned(iter) => iter.nth(n),
+        }
+    }
+}
+
+impl<'a, I, B> DoubleEndedIterator for ZipCowIter<'a, I, B>
+where
+    I: DoubleEndedIterator,
+    B: ToOwned + ?Sized,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match &mut self.inner {
+            CowInner::Borrowed(iter, borrowed) => {
+                let borrowed = *borrowed;
+                iter.next_back().map(|item| (item, Cow::Borrowed(borrowed)))
+            }
+            CowInner::Owned(iter) => iter.next_back(),
+        }
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        match &mut self.inner {
+            CowInner::Borrowed(iter, borrowed) => {
+                let borrowed = *borrowed;
+                iter.nth_back(n).map(|item| (item, Cow::Borrowed(borrowed)))
+            }
+            CowInner::Owned(iter) => iter.nth_back(n),
+        }
+    }
+}
+
+impl<'a, I, B> ExactSizeIterator for ZipCowIter<'a, I, B>
+where
+    I: ExactSizeIterator,
+    B: ToOwned + ?Sized,
+{
+}
+
+// The owned variant is always fused, but the borrowed variant is only fused if
+// the underlying iterator is.
+impl<'a, I, B> FusedIterator for ZipCowIter<'a, I, B>
+where
+    I: FusedIterator,
+    B: ToOwned + ?Sized,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use crate::tests::Clonable;
+    use crate::{zip_cow, zip_static_clone};
+
+    #[test]
+    fn test_cow_borrowed() {
+        let iter = 1..6;
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let mut n = 0;
+        for (_, c) in zip_cow(iter, Cow::Borrowed(&cloned)) {
+            assert!(matches!(c, Cow::Borrowed(_)));
+            n += 1;
+        }
+        assert_eq!(n, 5);
+        assert_eq!(count.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn test_cow_borrowed_nth_back() {
+        let mut iter = zip_cow(1..6, Cow::Borrowed("Hello"));
+        assert_eq!(iter.nth_back(1), Some((4, Cow::Borrowed("Hello"))));
+        assert_eq!(iter.nth_back(3), None);
+    }
+
+    #[test]
+    fn test_cow_owned() {
+        let iter = 1..6;
+        let count = AtomicU32::new(0);
+        let cloned: Cow<Clonable> = Cow::Owned(Clonable { count: &count });
+        assert_eq!(zip_cow(iter, cloned).map(|_| ()).count(), 5);
+        assert_eq!(count.load(Ordering::Relaxed), 4);
+    }
+
+    #[test]
+    fn test_zip_static_clone() {
+        static COUNT: AtomicU32 = AtomicU32::new(0);
+        static VALUE: Clonable<'static> = Clonable { count: &COUNT };
+        let n = zip_static_clone(1..6, &VALUE)
+            .filter(|(_, c)| matches!(c, Cow::Borrowed(_)))
+            .count();
+        assert_eq!(n, 5);
+        assert_eq!(COUNT.load(Ordering::Relaxed), 0);
+    }
+}
@@ -0,0 +1,156 @@
+use std::borrow::Borrow;
+use std::collections::hash_map::{self, HashMap, RandomState};
+use std::hash::{BuildHasher, Hash};
+
+/// A map in which every key has a value, that only clones a shared default
+/// value when a key is first written.
+///
+/// Looking up a key that was never written returns a reference to the default
+/// value. Mutable access to a key, using [`get_mut`](Self::get_mut), stores a
+/// clone of the default value for the key first.
+///
+/// Example:
+/// ```rust
+/// use zip_clone::CowMap;
+///
+/// let mut map = CowMap::new(vec![0u8; 1024]);
+/// map.get_mut("b")[0] = 1;
+/// assert_eq!(map.get("a")[0], 0);
+/// assert_eq!(map.get("b")[0], 1);
+/// assert_eq!(map.len(), 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct CowMap<K, V, S = RandomState> {
+    prototype: V,
+    map: HashMap<K, V, S>,
+}
+
+impl<K, V> CowMap<K, V, RandomState> {
+    /// Create an empty map with a default value.
+    pub fn new(prototype: V) -> Self {
+        CowMap {
+            prototype,
+            map: HashMap::new(),
+        }
+    }
+}
+
+impl<K, V, S> CowMap<K, V, S> {
+    /// Create an empty map with a default value, using `hasher` to hash keys.
+    pub fn with_hasher(prototype: V, hasher: S) -> Self {
+        CowMap {
+            prototype,
+            map: HashMap::with_hasher(hasher),
+        }
+    }
+
+    /// Return a reference to the default value.
+    pub fn prototype(&self) -> &V {
+        &self.prototype
+    }
+
+    /// Return the number of keys that have been written.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Return `true` if no keys have been written.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Return an iterator over the keys that have been written, and their
+    /// values.
+    pub fn iter(&self) -> hash_map::Iter<'_, K, V> {
+        self.map.iter()
+    }
+
+    /// Return the default value and a map of the keys that have been written.
+    pub fn into_parts(self) -> (V, HashMap<K, V, S>) {
+        (self.prototype, self.map)
+    }
+}
+
+impl<K, V, S> CowMap<K, V, S>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+{
+    /// Return a reference to the value for a key, which is the default value
+    /// if the key has not been written.
+    pub fn get<Q>(&self, key: &Q) -> &V
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.map.get(key).unwrap_or(&self.prototype)
+    }
+
+    /// Return `true` if the key has been written.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.map.contains_key(key)
+    }
+
+    /// Return a mutable reference to the value for a key, cloning the default
+    /// value for the key if it has not been written.
+    pub fn get_mut(&mut self, key: K) -> &mut V
+    where
+        V: Clone,
+    {
+        let prototype = &self.prototype;
+        self.map.entry(key).or_insert_with(|| prototype.clone())
+    }
+
+    /// Set the value for a key, returning the previous value if the key had
+    /// been written.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.map.insert(key, value)
+    }
+
+    /// Reset a key to the default value, returning its value if the key had
+    /// been written.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.map.remove(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use crate::tests::Clonable;
+    use crate::CowMap;
+
+    #[test]
+    fn test_cow_map() {
+        let count = AtomicU32::new(0);
+        let mut map = CowMap::new(Clonable { count: &count });
+        for key in 0..10 {
+            map.get(&key);
+        }
+        assert!(map.is_empty());
+        assert_eq!(count.load(Ordering::Relaxed), 0);
+        map.get_mut(1);
+        map.get_mut(1);
+        assert!(map.contains_key(&1));
+        assert_eq!(map.len(), 1);
+        assert_eq!(count.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn test_cow_map_remove() {
+        let mut map = CowMap::new(0);
+        *map.get_mut("a") = 5;
+        assert_eq!(*map.get("a"), 5);
+        assert_eq!(map.remove("a"), Some(5));
+        assert_eq!(*map.get("a"), 0);
+    }
+}
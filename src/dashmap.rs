@@ -0,0 +1,120 @@
+//! Insert clones of a value into a [`dashmap`](::dashmap) map from several
+//! threads.
+
+use std::hash::{BuildHasher, Hash};
+
+use ::dashmap::mapref::entry::Entry;
+use ::dashmap::DashMap;
+
+use crate::ZipClone;
+
+/// Set the value of every key in a concurrent map to a copy of a value,
+/// using several worker threads.
+///
+/// The keys are split into at most `num_workers` groups of similar size. Each
+/// group is inserted by a scoped thread that owns one clone of `value`, with
+/// the original value moved to the thread inserting the last group. Within a
+/// group, existing values are updated using [`Clone::clone_from`] to reuse
+/// their resources, and the last key receives the worker's own value.
+///
+/// Returns the number of keys set.
+///
+/// Example:
+/// ```rust
+/// use dashmap::DashMap;
+/// use zip_clone::dashmap::insert_clones_concurrent;
+///
+/// let map = DashMap::new();
+/// let n = insert_clones_concurrent(&map, 0..100, 4, String::from("Hello"));
+/// assert_eq!(n, 100);
+/// assert!(map.iter().all(|entry| entry.value() == "Hello"));
+/// ```
+pub fn insert_clones_concurrent<K, V, S, I>(
+    map: &DashMap<K, V, S>,
+    keys: I,
+    num_workers: usize,
+    value: V,
+) -> usize
+where
+    K: Eq + Hash + Send + Sync,
+    V: Clone + Send + Sync,
+    S: BuildHasher + Clone + Send + Sync,
+    I: IntoIterator<Item = K>,
+{
+    let mut keys = keys.into_iter().collect::<Vec<_>>();
+    let len = keys.len();
+    if len == 0 {
+        return 0;
+    }
+    let num_workers = num_workers.max(1);
+    let chunk_size = (len + num_workers - 1) / num_workers;
+    let mut chunks = Vec::with_capacity(num_workers);
+    while keys.len() > chunk_size {
+        chunks.push(keys.split_off(keys.len() - chunk_size));
+    }
+    chunks.push(keys);
+    std::thread::scope(|scope| {
+        for (chunk, value) in chunks.into_iter().zip_clone(value) {
+            scope.spawn(move || insert_chunk(map, chunk, value));
+        }
+    });
+    len
+}
+
+// Set each key to a copy of the value, with the last key receiving the value.
+fn insert_chunk<K, V, S>(map: &DashMap<K, V, S>, keys: Vec<K>, value: V)
+where
+    K: Eq + Hash,
+    V: Clone,
+    S: BuildHasher + Clone,
+{
+    let mut keys = keys.into_iter().peekable();
+    while let Some(key) = keys.next() {
+        let last = keys.peek().is_none();
+        match map.entry(key) {
+            Entry::Occupied(mut entry) if !last => entry.get_mut().clone_from(&value),
+            Entry::Vacant(entry) if !last => {
+                entry.insert(value.clone());
+            }
+            Entry::Occupied(mut entry) => {
+                entry.insert(value);
+                return;
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(value);
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use ::dashmap::DashMap;
+
+    use super::insert_clones_concurrent;
+    use crate::tests::Clonable;
+
+    #[test]
+    fn test_insert_clones_concurrent() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let map = DashMap::new();
+        assert_eq!(insert_clones_concurrent(&map, 0..100, 4, cloned), 100);
+        assert_eq!(map.len(), 100);
+        assert_eq!(count.load(Ordering::Relaxed), 99);
+    }
+
+    #[test]
+    fn test_insert_clones_concurrent_existing() {
+        let map = DashMap::new();
+        map.insert(1, String::with_capacity(64));
+        insert_clones_concurrent(&map, [1, 2, 3], 1, String::from("Hello"));
+        let existing = map.get(&1).unwrap();
+        assert_eq!(*existing, "Hello");
+        assert!(existing.capacity() >= 64);
+        assert_eq!(map.len(), 3);
+    }
+}
@@ -0,0 +1,128 @@
+/// Forward `Iterator` methods of a wrapper type to a field, keeping the
+/// clone-saving overrides of [`ZipCloneIter`](crate::ZipCloneIter).
+///
+/// A wrapper iterator that only implements `next` uses the default
+/// implementations of `last`, `nth`, `find` and `count`, which clone the value
+/// for every item they pass. Use this macro inside the `Iterator` impl of a
+/// wrapper to forward `next`, `size_hint`, `count`, `last`, `nth`, `find` and
+/// `fold` to the named field instead. Inside a `DoubleEndedIterator` impl, add
+/// `DoubleEndedIterator` to forward `next_back`, `nth_back` and `rfold`.
+///
+/// The wrapper must declare `type Item` with the same type as the field's
+/// items. Since `count`, `last` and `fold` take `self` by value, the wrapper
+/// cannot implement `Drop`.
+///
+/// Example:
+/// ```rust
+/// use zip_clone::{delegate_zip_clone, ZipClone, ZipCloneIter};
+///
+/// struct Labelled<I: Iterator> {
+///     inner: ZipCloneIter<I, String>,
+/// }
+///
+/// impl<I: Iterator> Iterator for Labelled<I> {
+///     type Item = (I::Item, String);
+///
+///     delegate_zip_clone!(inner);
+/// }
+///
+/// impl<I: DoubleEndedIterator> DoubleEndedIterator for Labelled<I> {
+///     delegate_zip_clone!(inner, DoubleEndedIterator);
+/// }
+///
+/// let iter = Labelled { inner: (0..10).zip_clone(String::from("Hello")) };
+/// assert_eq!(iter.rev().last(), Some((0, String::from("Hello"))));
+/// ```
+#[macro_export]
+macro_rules! delegate_zip_clone {
+    ($field:ident) => {
+        fn next(&mut self) -> ::core::option::Option<Self::Item> {
+            ::core::iter::Iterator::next(&mut self.$field)
+        }
+
+        fn size_hint(&self) -> (usize, ::core::option::Option<usize>) {
+            ::core::iter::Iterator::size_hint(&self.$field)
+        }
+
+        fn count(self) -> usize {
+            ::core::iter::Iterator::count(self.$field)
+        }
+
+        fn last(self) -> ::core::option::Option<Self::Item> {
+            ::core::iter::Iterator::last(self.$field)
+        }
+
+        fn nth(&mut self, n: usize) -> ::core::option::Option<Self::Item> {
+            ::core::iter::Iterator::nth(&mut self.$field, n)
+        }
+
+        fn find<P>(&mut self, predicate: P) -> ::core::option::Option<Self::Item>
+        where
+            P: FnMut(&Self::Item) -> bool,
+        {
+            ::core::iter::Iterator::find(&mut self.$field, predicate)
+        }
+
+        fn fold<B, F>(self, init: B, f: F) -> B
+        where
+            F: FnMut(B, Self::Item) -> B,
+        {
+            ::core::iter::Iterator::fold(self.$field, init, f)
+        }
+    };
+    ($field:ident, DoubleEndedIterator) => {
+        fn next_back(&mut self) -> ::core::option::Option<Self::Item> {
+            ::core::iter::DoubleEndedIterator::next_back(&mut self.$field)
+        }
+
+        fn nth_back(&mut self, n: usize) -> ::core::option::Option<Self::Item> {
+            ::core::iter::DoubleEndedIterator::nth_back(&mut self.$field, n)
+        }
+
+        fn rfold<B, F>(self, init: B, f: F) -> B
+        where
+            F: FnMut(B, Self::Item) -> B,
+        {
+            ::core::iter::DoubleEndedIterator::rfold(self.$field, init, f)
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use crate::tests::Clonable;
+    use crate::{zip_clone, ZipCloneIter};
+
+    struct Wrapper<'a, I: Iterator> {
+        inner: ZipCloneIter<I, Clonable<'a>>,
+    }
+
+    impl<'a, I: Iterator> Iterator for Wrapper<'a, I> {
+        type Item = (I::Item, Clonable<'a>);
+
+        delegate_zip_clone!(inner);
+    }
+
+    impl<'a, I: DoubleEndedIterator> DoubleEndedIterator for Wrapper<'a, I> {
+        delegate_zip_clone!(inner, DoubleEndedIterator);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "predictable", ignore)]
+    fn test_delegate_zip_clone() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let mut iter = Wrapper {
+            inner: zip_clone(0..10, cloned),
+        };
+        assert_eq!(iter.find(|(i, _)| *i == 5).map(|(i, _)| i), Some(5));
+        assert_eq!(iter.nth_back(1).map(|(i, _)| i), Some(8));
+        assert_eq!(iter.len(), 2);
+        assert_eq!(iter.last().map(|(i, _)| i), Some(7));
+        assert_eq!(count.load(Ordering::Relaxed), 2);
+    }
+
+    impl<I: ExactSizeIterator> ExactSizeIterator for Wrapper<'_, I> {}
+}
@@ -0,0 +1,430 @@
+#[cfg(feature = "alloc")]
+use alloc::collections::TryReserveError;
+#[cfg(feature = "alloc")]
+use alloc::rc::Rc;
+#[cfg(feature = "alloc")]
+use alloc::sync::Arc;
+#[cfg(feature = "alloc")]
+use alloc::vec::{self, Vec};
+#[cfg(feature = "alloc")]
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+
+#[cfg(feature = "alloc")]
+use crate::TryClone;
+use crate::{Held, Lookahead, ZipCloneIter};
+
+/// A strategy for creating copies of a value.
+///
+/// A [`ZipCloneIter`] holds the value and calls
+/// [`duplicate`](Self::duplicate) for each item except the last, which
+/// receives what is left of the value. Every adapter and override of
+/// `ZipCloneIter` works with every strategy: plain cloning ([`ByClone`], the
+/// default), fallible cloning ([`ByTryClone`]), reference counted sharing
+/// (`ByClone` of an `Rc` or `Arc`), splitting off part of the value
+/// ([`BySplit`] and [`ByDistribute`]), and creating values with a factory
+/// ([`ByFactory`]).
+///
+/// Like clones, copies are only made for items that are returned, so
+/// skipping items with `nth`, `count` or `last` leaves more of a split value
+/// for the remaining items.
+pub trait Duplicate<C> {
+    /// Create a copy of the value for an item that is not the last, leaving
+    /// in `value` what is kept for the following items.
+    fn duplicate(&mut self, value: &mut C) -> C;
+}
+
+/// Duplicate a value by cloning it.
+///
+/// The first item receives the original and a clone is kept for the
+/// following items. If `clone` panics, the value is left in place.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ByClone;
+
+impl<C> Duplicate<C> for ByClone
+where
+    C: Clone,
+{
+    #[inline]
+    fn duplicate(&mut self, value: &mut C) -> C {
+        let cloned = value.clone();
+        core::mem::replace(value, cloned)
+    }
+}
+
+/// Duplicate a value by cloning it with [`TryClone`].
+///
+/// The value is held as `Ok(value)`. If a clone fails, the error is given to
+/// that item and the value is kept, so the next item tries again.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ByTryClone;
+
+#[cfg(feature = "alloc")]
+impl<C> Duplicate<Result<C, TryReserveError>> for ByTryClone
+where
+    C: TryClone,
+{
+    fn duplicate(&mut self, value: &mut Result<C, TryReserveError>) -> Result<C, TryReserveError> {
+        match value {
+            Ok(value) => {
+                let cloned = value.try_clone()?;
+                Ok(core::mem::replace(value, cloned))
+            }
+            Err(e) => Err(e.clone()),
+        }
+    }
+}
+
+/// Duplicate a value by splitting off part of it for each item, giving the
+/// remainder to the last item.
+#[derive(Debug, Clone)]
+pub struct BySplit<F>(pub F);
+
+impl<C, F> Duplicate<C> for BySplit<F>
+where
+    F: FnMut(&mut C) -> C,
+{
+    fn duplicate(&mut self, value: &mut C) -> C {
+        (self.0)(value)
+    }
+}
+
+/// A value whose ownership can be divided between several owners without
+/// cloning its contents.
+///
+/// For reference-counted pointers, a share is another pointer to the same
+/// value. Structs whose fields all implement this trait can derive it with
+/// `#[derive(DistributeOwnership)]` when the `macros` feature is enabled.
+pub trait DistributeOwnership: Sized {
+    /// Split off a share of the value for another owner, keeping the rest.
+    fn distribute(&mut self) -> Self;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> DistributeOwnership for Rc<T> {
+    fn distribute(&mut self) -> Self {
+        Rc::clone(self)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> DistributeOwnership for Arc<T> {
+    fn distribute(&mut self) -> Self {
+        Arc::clone(self)
+    }
+}
+
+impl<T> DistributeOwnership for Option<T>
+where
+    T: DistributeOwnership,
+{
+    fn distribute(&mut self) -> Self {
+        self.as_mut().map(T::distribute)
+    }
+}
+
+impl<T> DistributeOwnership for PhantomData<T> {
+    fn distribute(&mut self) -> Self {
+        PhantomData
+    }
+}
+
+impl DistributeOwnership for () {
+    fn distribute(&mut self) -> Self {}
+}
+
+/// Duplicate a value by distributing shares of its ownership, giving the
+/// remaining share to the last item.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ByDistribute;
+
+impl<C> Duplicate<C> for ByDistribute
+where
+    C: DistributeOwnership,
+{
+    fn duplicate(&mut self, value: &mut C) -> C {
+        value.distribute()
+    }
+}
+
+/// Create a new value for every item after the first using a function.
+///
+/// Each item receives the value held for it, which is replaced by a new
+/// value for the following items, so the factory is called once for each
+/// item except the last.
+#[derive(Debug, Clone)]
+pub struct ByFactory<F>(pub F);
+
+impl<C, F> Duplicate<C> for ByFactory<F>
+where
+    F: FnMut() -> C,
+{
+    fn duplicate(&mut self, value: &mut C) -> C {
+        core::mem::replace(value, (self.0)())
+    }
+}
+
+/// Zip an iterator to copies of a value created by a strategy.
+///
+/// This generalizes [`zip_clone`](crate::zip_clone) to any [`Duplicate`]
+/// strategy. `zip_duplicate(iter, value, ByClone)` is `zip_clone(iter,
+/// value)`.
+///
+/// Example:
+/// ```rust
+/// use zip_clone::{zip_duplicate, BySplit};
+///
+/// // Give each worker 10 units of a budget, and the last worker the rest.
+/// let budget = BySplit(|budget: &mut u32| {
+///     let part = (*budget).min(10);
+///     *budget -= part;
+///     part
+/// });
+/// let shares = zip_duplicate(0..4, 45, budget)
+///     .map(|(_, share)| share)
+///     .collect::<Vec<_>>();
+/// assert_eq!(shares, [10, 10, 10, 15]);
+/// ```
+pub fn zip_duplicate<I, C, D>(iter: I, value: C, strategy: D) -> ZipCloneIter<I, C, D>
+where
+    I: Iterator,
+    D: Duplicate<C>,
+{
+    ZipCloneIter {
+        iter: Lookahead::new(iter.peekable()),
+        cloned: Held::new(value),
+        strategy,
+    }
+}
+
+/// Zip an iterator to parts of a value split in proportion to a weight for
+/// each item.
+///
+/// `weight` returns the expected need of each item. `split` is called for
+/// each item except the last with the value, the item's weight, and the total
+/// weight of this and all following items. It removes and returns a part of
+/// the value for the item. The last item receives what remains. The total
+/// weight is zero only if all remaining weights are zero, so `split` must
+/// handle that case.
+///
+/// Since the total weight is needed before the first split, the items are
+/// collected before the first item is returned.
+///
+/// Example:
+/// ```rust
+/// use zip_clone::zip_split_weighted;
+///
+/// // Divide a capacity budget between buffers in proportion to their needs.
+/// let needs = vec![10, 30, 60];
+/// let shares = zip_split_weighted(
+///     needs,
+///     1000,
+///     |need: &usize| *need,
+///     |budget: &mut usize, weight, total| {
+///         let part = *budget * weight / total;
+///         *budget -= part;
+///         part
+///     },
+/// )
+/// .map(|(_, share)| share)
+/// .collect::<Vec<_>>();
+/// assert_eq!(shares, [100, 300, 600]);
+/// ```
+///
+/// # Panics
+///
+/// Panics if the total weight overflows `usize`.
+#[cfg(feature = "alloc")]
+pub fn zip_split_weighted<I, C, W, F>(
+    iter: I,
+    value: C,
+    mut weight: W,
+    split: F,
+) -> ZipSplitWeighted<I::Item, C, F>
+where
+    I: IntoIterator,
+    W: FnMut(&I::Item) -> usize,
+    F: FnMut(&mut C, usize, usize) -> C,
+{
+    let mut total = 0usize;
+    let items = iter
+        .into_iter()
+        .map(|item| {
+            let weight = weight(&item);
+            total = total
+                .checked_add(weight)
+                .expect("total weight overflows usize");
+            (item, weight)
+        })
+        .collect::<Vec<_>>();
+    ZipSplitWeighted {
+        items: items.into_iter(),
+        remaining: total,
+        value: Some(value),
+        split,
+    }
+}
+
+/// An iterator that pairs items with parts of a value split in proportion to
+/// their weights.
+///
+/// This struct is created by [`zip_split_weighted`].
+#[cfg(feature = "alloc")]
+pub struct ZipSplitWeighted<T, C, F> {
+    items: vec::IntoIter<(T, usize)>,
+    // The total weight of the items not yet returned.
+    remaining: usize,
+    value: Option<C>,
+    split: F,
+}
+
+#[cfg(feature = "alloc")]
+impl<T, C, F> Iterator for ZipSplitWeighted<T, C, F>
+where
+    F: FnMut(&mut C, usize, usize) -> C,
+{
+    type Item = (T, C);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (item, weight) = self.items.next()?;
+        let part = if self.items.len() == 0 {
+            self.value.take()?
+        } else {
+            let part = (self.split)(self.value.as_mut()?, weight, self.remaining);
+            self.remaining -= weight;
+            part
+        };
+        Some((item, part))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.items.size_hint()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, C, F> ExactSizeIterator for ZipSplitWeighted<T, C, F> where
+    F: FnMut(&mut C, usize, usize) -> C
+{
+}
+
+#[cfg(feature = "alloc")]
+impl<T, C, F> FusedIterator for ZipSplitWeighted<T, C, F> where F: FnMut(&mut C, usize, usize) -> C {}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
+
+    use crate::tests::Clonable;
+    use crate::{
+        zip_duplicate, zip_split_weighted, ByClone, ByDistribute, ByFactory, BySplit, ByTryClone,
+    };
+
+    #[test]
+    fn test_zip_duplicate_clone() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        assert_eq!(zip_duplicate(0..5, cloned, ByClone).map(|_| ()).count(), 5);
+        assert_eq!(count.load(Ordering::Relaxed), 4);
+    }
+
+    #[test]
+    fn test_zip_duplicate_shared() {
+        let count = AtomicU32::new(0);
+        let shared = Arc::new(Clonable { count: &count });
+        let values = zip_duplicate(0..5, shared, ByClone)
+            .map(|(_, value)| value)
+            .collect::<Vec<_>>();
+        assert_eq!(Arc::strong_count(&values[0]), 5);
+        assert_eq!(count.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn test_zip_duplicate_distribute() {
+        let count = AtomicU32::new(0);
+        let shared = Some(Arc::new(Clonable { count: &count }));
+        let values = zip_duplicate(0..5, shared, ByDistribute)
+            .map(|(_, value)| value)
+            .collect::<Vec<_>>();
+        assert_eq!(Arc::strong_count(values[0].as_ref().unwrap()), 5);
+        assert_eq!(count.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn test_zip_duplicate_try_clone() {
+        let mut iter = zip_duplicate(0..3, Ok(vec![1, 2, 3]), ByTryClone);
+        assert!(iter.all(|(_, value)| value.is_ok()));
+    }
+
+    #[test]
+    fn test_zip_duplicate_factory() {
+        let mut next = 1;
+        let factory = ByFactory(|| {
+            next += 1;
+            next
+        });
+        let values = zip_duplicate(0..3, 1, factory)
+            .map(|(_, value)| value)
+            .collect::<Vec<_>>();
+        assert_eq!(values, [1, 2, 3]);
+    }
+
+    #[test]
+    fn test_zip_duplicate_split_adapters() {
+        // The adapters and overrides of `ZipCloneIter` use the strategy too.
+        let halve = BySplit(|budget: &mut u32| {
+            let part = *budget / 2;
+            *budget -= part;
+            part
+        });
+        let mut iter = zip_duplicate(0..10, 64, halve).filter_items(|i| i % 3 == 0);
+        assert_eq!(iter.next(), Some((0, 32)));
+        // Without the `predictable` feature, `find` lends the value to the
+        // rejected item, so it is not split for it.
+        let share = if cfg!(feature = "predictable") { 8 } else { 16 };
+        assert_eq!(iter.find(|&(i, _)| i == 6), Some((6, share)));
+        assert_eq!(iter.fold(0, |acc, (_, share)| acc + share), share);
+    }
+
+    #[test]
+    fn test_zip_split_weighted() {
+        // Reserve buffer capacity in proportion to each item's length.
+        let words = vec!["a", "bbb", "", "cccc"];
+        let buffers = zip_split_weighted(
+            words,
+            Vec::<u8>::with_capacity(80),
+            |word: &&str| word.len(),
+            |buffer: &mut Vec<u8>, weight, total| {
+                let part = buffer.capacity() * weight / total;
+                let rest = buffer.capacity() - part;
+                *buffer = Vec::with_capacity(rest);
+                Vec::with_capacity(part)
+            },
+        )
+        .map(|(word, buffer)| (word, buffer.capacity()))
+        .collect::<Vec<_>>();
+        assert_eq!(buffers[0], ("a", 10));
+        assert_eq!(buffers[1], ("bbb", 30));
+        assert_eq!(buffers[2], ("", 0));
+        assert!(buffers[3].1 >= 40);
+    }
+
+    #[test]
+    fn test_zip_split_weighted_zero() {
+        let shares = zip_split_weighted(
+            0..3,
+            9,
+            |_| 0,
+            |budget: &mut u32, _, total| {
+                assert_eq!(total, 0);
+                *budget -= 3;
+                3
+            },
+        )
+        .map(|(_, share)| share)
+        .collect::<Vec<_>>();
+        assert_eq!(shares, [3, 3, 3]);
+    }
+}
@@ -0,0 +1,74 @@
+//! Route items paired with clones into [`Either`] sides.
+
+use ::either::Either;
+
+use crate::zip_clone;
+
+/// Map each item, together with a copy of a value, to either a left or a right
+/// result, and collect each side into its own collection.
+///
+/// This is itertools' `partition_map` for items zipped with clones. The value
+/// is cloned for each item except the last, whose mapping receives the
+/// original.
+///
+/// Example:
+/// ```rust
+/// use either::Either;
+/// use zip_clone::either::partition_map_clone;
+///
+/// let records = ["1", "x", "3"];
+/// let (matched, unmatched): (Vec<_>, Vec<_>) = partition_map_clone(
+///     records.iter(),
+///     String::from("batch-7"),
+///     |record, batch| match record.parse::<u32>() {
+///         Ok(n) => Either::Left((n, batch)),
+///         Err(_) => Either::Right((*record, batch)),
+///     },
+/// );
+/// assert_eq!(matched.len(), 2);
+/// assert_eq!(unmatched, [("x", String::from("batch-7"))]);
+/// ```
+pub fn partition_map_clone<I, C, A, B, L, R, F>(iter: I, value: C, mut f: F) -> (A, B)
+where
+    I: Iterator,
+    C: Clone,
+    A: Default + Extend<L>,
+    B: Default + Extend<R>,
+    F: FnMut(I::Item, C) -> Either<L, R>,
+{
+    let mut left = A::default();
+    let mut right = B::default();
+    for (item, value) in zip_clone(iter, value) {
+        match f(item, value) {
+            Either::Left(l) => left.extend(Some(l)),
+            Either::Right(r) => right.extend(Some(r)),
+        }
+    }
+    (left, right)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use ::either::Either;
+
+    use super::partition_map_clone;
+    use crate::tests::Clonable;
+
+    #[test]
+    fn test_partition_map_clone() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let (even, odd): (Vec<_>, Vec<_>) = partition_map_clone(0..10, cloned, |i, c| {
+            if i % 2 == 0 {
+                Either::Left((i, c))
+            } else {
+                Either::Right(c)
+            }
+        });
+        assert_eq!(even.len(), 5);
+        assert_eq!(odd.len(), 5);
+        assert_eq!(count.load(Ordering::Relaxed), 9);
+    }
+}
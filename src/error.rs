@@ -0,0 +1,141 @@
+//! Error types returned by fallible operations.
+//!
+//! Operations that stop at an error return the value that would have been
+//! given to the next item with the error, so that it can be reused, for
+//! example to retry.
+
+use core::fmt;
+
+#[cfg(feature = "alloc")]
+pub use alloc::collections::TryReserveError;
+
+/// The error returned when zipping would make more clones than a
+/// [`CloneBudget`](crate::CloneBudget) allows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CloneBudgetExceeded {
+    clones: usize,
+    max: usize,
+}
+
+impl CloneBudgetExceeded {
+    pub(crate) fn new(clones: usize, max: usize) -> Self {
+        CloneBudgetExceeded { clones, max }
+    }
+
+    /// Return the number of clones that zipping would make.
+    pub fn clones(&self) -> usize {
+        self.clones
+    }
+
+    /// Return the maximum number of clones allowed.
+    pub fn max(&self) -> usize {
+        self.max
+    }
+}
+
+impl fmt::Display for CloneBudgetExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} clones needed, more than the budget of {}",
+            self.clones, self.max
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CloneBudgetExceeded {}
+
+/// An error together with the value recovered when it occurred.
+///
+/// This is returned by [`try_map_clone`](crate::try_map_clone), where the
+/// value is always available, and by
+/// [`ZipCloneIter::try_for_each_clone`](crate::ZipCloneIter::try_for_each_clone),
+/// where the value is an `Option` that is `None` if the error occurred for the
+/// last item.
+///
+/// `Display` and `Error` are forwarded to the inner error, so the value does
+/// not need to implement them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecoverableError<E, C> {
+    error: E,
+    value: C,
+}
+
+impl<E, C> RecoverableError<E, C> {
+    /// Create an error that carries a recovered value.
+    pub fn new(error: E, value: C) -> Self {
+        RecoverableError { error, value }
+    }
+
+    /// Return a reference to the error.
+    pub fn error(&self) -> &E {
+        &self.error
+    }
+
+    /// Return a reference to the recovered value.
+    pub fn value(&self) -> &C {
+        &self.value
+    }
+
+    /// Return the error, dropping the recovered value.
+    pub fn into_error(self) -> E {
+        self.error
+    }
+
+    /// Return the recovered value, dropping the error.
+    pub fn into_value(self) -> C {
+        self.value
+    }
+
+    /// Return the error and the recovered value.
+    pub fn into_parts(self) -> (E, C) {
+        (self.error, self.value)
+    }
+}
+
+impl<E, C> fmt::Display for RecoverableError<E, C>
+where
+    E: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.error.fmt(f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E, C> std::error::Error for RecoverableError<E, C>
+where
+    E: std::error::Error + 'static,
+    C: fmt::Debug,
+{
+    // `Display` already shows the inner error, so it is not also returned by
+    // `source`, which would make error reporters print its message twice.
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use std::error::Error;
+    use std::fmt;
+
+    use super::RecoverableError;
+
+    #[derive(Debug)]
+    struct Failed;
+
+    impl fmt::Display for Failed {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("failed")
+        }
+    }
+
+    impl Error for Failed {}
+
+    #[test]
+    fn test_recoverable_error() {
+        let e = RecoverableError::new(Failed, String::from("Hello"));
+        assert_eq!(e.to_string(), "failed");
+        assert!(e.source().is_none());
+        assert_eq!(e.into_value(), "Hello");
+    }
+}
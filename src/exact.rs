@@ -0,0 +1,89 @@
+#[cfg(feature = "unsafe-opt")]
+use core::mem::{ManuallyDrop, MaybeUninit};
+#[cfg(feature = "unsafe-opt")]
+use core::ptr;
+
+/// Zip an array to clones of a value, returning an array of pairs.
+///
+/// The length is known at compile time, so the value is cloned exactly
+/// `N - 1` times, with the original paired with the last item, and no
+/// lookahead is needed to find the last item. With the `unsafe-opt` feature
+/// the first `N - 1` pairs are written with clones and the original is moved
+/// into the last pair, with no per-item state. The safe build counts down to
+/// the last item instead, which the compiler can unroll for small arrays.
+///
+/// Example:
+/// ```rust
+/// use zip_clone::zip_clone_exact;
+///
+/// let pairs = zip_clone_exact([1, 2, 3], String::from("Hello"));
+/// assert_eq!(pairs[2], (3, String::from("Hello")));
+/// for (i, s) in pairs {
+///     assert_eq!(s, "Hello");
+/// }
+/// ```
+pub fn zip_clone_exact<T, C, const N: usize>(items: [T; N], value: C) -> [(T, C); N]
+where
+    C: Clone,
+{
+    #[cfg(feature = "unsafe-opt")]
+    {
+        let mut items = ManuallyDrop::new(items);
+        let mut pairs: [MaybeUninit<(T, C)>; N] = [(); N].map(|_| MaybeUninit::uninit());
+        if let Some(last) = N.checked_sub(1) {
+            // A panic in `clone` leaks the pairs already written and the items
+            // not yet read, but never drops anything twice.
+            for (pair, item) in pairs[..last].iter_mut().zip(&mut items[..last]) {
+                // SAFETY: each item is read exactly once and `items` is never
+                // dropped.
+                pair.write((unsafe { ptr::read(item) }, value.clone()));
+            }
+            // SAFETY: as above, the last item has not been read yet.
+            pairs[last].write((unsafe { ptr::read(&items[last]) }, value));
+        }
+        // SAFETY: all `N` pairs were initialized above, and `MaybeUninit<(T, C)>`
+        // has the same layout as `(T, C)`.
+        unsafe { ptr::read(&pairs as *const _ as *const [(T, C); N]) }
+    }
+    #[cfg(not(feature = "unsafe-opt"))]
+    {
+        let mut value = Some(value);
+        let mut remaining = N;
+        items.map(|item| {
+            remaining -= 1;
+            let value = if remaining == 0 {
+                value.take()
+            } else {
+                value.clone()
+            };
+            // The value is only taken for the last item.
+            (item, value.unwrap())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use crate::tests::Clonable;
+    use crate::zip_clone_exact;
+
+    #[test]
+    fn test_zip_clone_exact() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let pairs = zip_clone_exact([1, 2, 3, 4, 5], cloned);
+        assert_eq!(pairs.len(), 5);
+        assert_eq!(count.load(Ordering::Relaxed), 4);
+    }
+
+    #[test]
+    fn test_zip_clone_exact_empty() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let pairs: [(u8, _); 0] = zip_clone_exact([], cloned);
+        assert!(pairs.is_empty());
+        assert_eq!(count.load(Ordering::Relaxed), 0);
+    }
+}
@@ -0,0 +1,581 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "unsafe-opt")]
+use core::mem::MaybeUninit;
+#[cfg(feature = "std")]
+use std::collections::hash_map::{Entry, HashMap};
+#[cfg(feature = "std")]
+use std::hash::{BuildHasher, Hash};
+
+use crate::fill_from_prototype;
+#[cfg(feature = "alloc")]
+use crate::repeat_clones;
+
+/// Insert a value for each key that is missing from a map.
+///
+/// Keys that are already present keep their existing value. Each missing key
+/// is inserted when the next missing key is found, so the value is cloned for
+/// each missing key except the last, which receives the original. Returns the
+/// number of keys inserted.
+///
+/// Example:
+/// ```rust
+/// use std::collections::HashMap;
+/// use zip_clone::get_or_insert_clones;
+///
+/// let mut map = HashMap::new();
+/// map.insert("a", String::from("existing"));
+/// let inserted = get_or_insert_clones(&mut map, ["a", "b", "c"], String::from("Hello"));
+/// assert_eq!(inserted, 2);
+/// assert_eq!(map["a"], "existing");
+/// assert_eq!(map["c"], "Hello");
+/// ```
+#[cfg(feature = "std")]
+pub fn get_or_insert_clones<K, V, S, I>(map: &mut HashMap<K, V, S>, keys: I, value: V) -> usize
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+    V: Clone,
+    I: IntoIterator<Item = K>,
+{
+    let mut inserted = 0;
+    // The last missing key found, which is not inserted yet.
+    let mut pending = None;
+    for key in keys {
+        if pending.as_ref() == Some(&key) {
+            continue;
+        }
+        if let Entry::Vacant(entry) = map.entry(key) {
+            if let Some(previous) = pending.replace(entry.into_key()) {
+                map.insert(previous, value.clone());
+            }
+            inserted += 1;
+        }
+    }
+    if let Some(key) = pending {
+        map.insert(key, value);
+    }
+    inserted
+}
+
+/// Fill the empty slots of a collection of `Option`s with a value.
+///
+/// Slots containing `Some` are left untouched. The value is cloned for each
+/// empty slot except the last, which receives the original. Returns the number
+/// of slots filled.
+///
+/// Example:
+/// ```rust
+/// use zip_clone::fill_none_with_clones;
+///
+/// let mut slots = vec![None, Some(String::from("set")), None];
+/// let filled = fill_none_with_clones(&mut slots, String::from("default"));
+/// assert_eq!(filled, 2);
+/// assert_eq!(slots[0].as_deref(), Some("default"));
+/// assert_eq!(slots[1].as_deref(), Some("set"));
+/// ```
+pub fn fill_none_with_clones<'a, C, I>(slots: I, value: C) -> usize
+where
+    C: Clone + 'a,
+    I: IntoIterator<Item = &'a mut Option<C>>,
+{
+    fill_from_prototype(slots.into_iter().filter(|slot| slot.is_none()), value)
+}
+
+/// Replace every element of a slice that matches a predicate with a value.
+///
+/// Each matching element except the last is overwritten using
+/// [`Clone::clone_from`], so it can reuse its own allocation. The last
+/// matching element receives the original value. Returns the number of
+/// elements replaced.
+///
+/// Example:
+/// ```rust
+/// use zip_clone::replace_matching;
+///
+/// let mut fields = ["alice@example.com", "42", "bob@example.com"].map(String::from);
+/// let replaced = replace_matching(&mut fields, |f| f.contains('@'), String::from("<redacted>"));
+/// assert_eq!(replaced, 2);
+/// assert_eq!(fields, ["<redacted>", "42", "<redacted>"]);
+/// ```
+pub fn replace_matching<T, P>(slice: &mut [T], mut predicate: P, value: T) -> usize
+where
+    T: Clone,
+    P: FnMut(&T) -> bool,
+{
+    fill_from_prototype(slice.iter_mut().filter(|item| predicate(item)), value)
+}
+
+/// Create a grid of `rows` vectors, each containing `cols` copies of a value.
+///
+/// The value is cloned `rows * cols - 1` times, with the original used for the
+/// last cell.
+///
+/// Example:
+/// ```rust
+/// use zip_clone::grid_of_clones;
+///
+/// let grid = grid_of_clones(3, 4, String::from("."));
+/// assert_eq!(grid.len(), 3);
+/// assert!(grid.iter().all(|row| row.len() == 4 && row[0] == "."));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn grid_of_clones<C>(rows: usize, cols: usize, value: C) -> Vec<Vec<C>>
+where
+    C: Clone,
+{
+    let mut cells = flat_grid_of_clones_iter(rows, cols, value);
+    let mut grid = Vec::with_capacity(rows);
+    for _ in 0..rows {
+        grid.push(cells.by_ref().take(cols).collect());
+    }
+    grid
+}
+
+/// Create a row-major grid of `rows * cols` copies of a value in one vector.
+///
+/// The value is cloned `rows * cols - 1` times, with the original used for the
+/// last cell. The cell at row `r` and column `c` is at index `r * cols + c`.
+///
+/// Example:
+/// ```rust
+/// use zip_clone::flat_grid_of_clones;
+///
+/// let grid = flat_grid_of_clones(3, 4, String::from("."));
+/// assert_eq!(grid.len(), 12);
+/// assert_eq!(grid[2 * 4 + 3], ".");
+/// ```
+#[cfg(feature = "alloc")]
+pub fn flat_grid_of_clones<C>(rows: usize, cols: usize, value: C) -> Vec<C>
+where
+    C: Clone,
+{
+    flat_grid_of_clones_iter(rows, cols, value).collect()
+}
+
+#[cfg(feature = "alloc")]
+fn flat_grid_of_clones_iter<C>(rows: usize, cols: usize, value: C) -> impl Iterator<Item = C>
+where
+    C: Clone,
+{
+    let cells = rows.checked_mul(cols).expect("capacity overflow");
+    repeat_clones(value, cells)
+}
+
+/// Append `n` copies of a value to a vector.
+///
+/// The copies are written directly into the vector's spare capacity. The value
+/// is cloned `n - 1` times, with the original placed last.
+///
+/// If a clone panics, the clones already written are dropped and the vector is
+/// left unchanged, apart from possibly having a larger capacity.
+///
+/// Example:
+/// ```rust
+/// use zip_clone::fill_spare_with_clones;
+///
+/// let mut v = vec![String::from("first")];
+/// fill_spare_with_clones(&mut v, 3, String::from("Hello"));
+/// assert_eq!(v, ["first", "Hello", "Hello", "Hello"]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn fill_spare_with_clones<C>(vec: &mut Vec<C>, n: usize, value: C)
+where
+    C: Clone,
+{
+    vec.reserve(n);
+    #[cfg(feature = "unsafe-opt")]
+    {
+        let len = vec.len();
+        write_clones_uninit(&mut vec.spare_capacity_mut()[..n], value);
+        // SAFETY: the `n` elements following the current length were
+        // initialized above and are within the capacity reserved above.
+        unsafe { vec.set_len(len + n) };
+    }
+    #[cfg(not(feature = "unsafe-opt"))]
+    {
+        let mut guard = TruncateGuard {
+            len: vec.len(),
+            vec,
+        };
+        guard.vec.extend(repeat_clones(value, n));
+        guard.len = guard.vec.len();
+    }
+}
+
+// Truncates a vector to its original length if appending to it is abandoned
+// by a panic.
+#[cfg(all(feature = "alloc", not(feature = "unsafe-opt")))]
+struct TruncateGuard<'a, C> {
+    vec: &'a mut Vec<C>,
+    len: usize,
+}
+
+#[cfg(all(feature = "alloc", not(feature = "unsafe-opt")))]
+impl<'a, C> Drop for TruncateGuard<'a, C> {
+    fn drop(&mut self) {
+        self.vec.truncate(self.len);
+    }
+}
+
+/// Push a copy of a value onto each of several vectors.
+///
+/// The value is cloned for each vector except the last, which receives the
+/// original. Returns the number of vectors pushed to.
+///
+/// Example:
+/// ```rust
+/// use zip_clone::push_clone_to_each;
+///
+/// let mut shards = vec![Vec::new(); 3];
+/// assert_eq!(push_clone_to_each(&mut shards, String::from("event")), 3);
+/// assert!(shards.iter().all(|shard| shard == &["event"]));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn push_clone_to_each<'a, C, I>(vecs: I, value: C) -> usize
+where
+    C: Clone + 'a,
+    I: IntoIterator<Item = &'a mut Vec<C>>,
+{
+    let mut vecs = vecs.into_iter().peekable();
+    let mut pushed = 0;
+    while let Some(vec) = vecs.next() {
+        pushed += 1;
+        if vecs.peek().is_none() {
+            vec.push(value);
+            break;
+        }
+        vec.push(value.clone());
+    }
+    pushed
+}
+
+/// Insert copies of a value into a vector at several positions.
+///
+/// `indices` are the positions of the inserted values in the resulting
+/// vector, in strictly increasing order. Existing elements keep their order
+/// and shift right to make room. The elements are moved in a single pass from
+/// right to left, instead of once for each insertion as with repeated calls to
+/// [`Vec::insert`]. Inserting `k` values uses `k - 1` clones.
+///
+/// # Panics
+///
+/// Panics if the indices are not strictly increasing, or if an index is
+/// beyond the end of the resulting vector.
+///
+/// Example:
+/// ```rust
+/// use zip_clone::insert_clones_at;
+///
+/// let mut v = vec!["a", "b", "c"].into_iter().map(String::from).collect::<Vec<_>>();
+/// insert_clones_at(&mut v, &[0, 2, 5], String::from("-"));
+/// assert_eq!(v, ["-", "a", "-", "b", "c", "-"]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn insert_clones_at<C>(vec: &mut Vec<C>, indices: &[usize], value: C)
+where
+    C: Clone,
+{
+    let inserts = indices.len();
+    let len = vec.len() + inserts;
+    assert!(
+        indices.windows(2).all(|pair| pair[0] < pair[1]),
+        "indices must be strictly increasing"
+    );
+    if let Some(&last) = indices.last() {
+        assert!(
+            last < len,
+            "insertion index (is {}) should be < len (is {})",
+            last,
+            len
+        );
+    }
+    vec.extend(repeat_clones(value, inserts));
+    // Elements from `read` to `write` hold inserted values not yet in place.
+    // Moving down from the end, each position either keeps an inserted value
+    // or swaps in the next existing element.
+    let mut read = len - inserts;
+    let mut write = len;
+    for &index in indices.iter().rev() {
+        write -= 1;
+        while write > index {
+            read -= 1;
+            vec.swap(read, write);
+            write -= 1;
+        }
+    }
+}
+
+/// Initialize a slice of uninitialized slots with copies of a value.
+///
+/// The value is cloned for each slot except the last, which receives the
+/// original. Returns the initialized slice. If the slice is empty, the value is
+/// dropped.
+///
+/// If a clone panics, the clones already written are dropped before the panic
+/// continues, leaving all slots uninitialized.
+///
+/// Example:
+/// ```rust
+/// use std::mem::MaybeUninit;
+/// use zip_clone::write_clones_uninit;
+///
+/// let mut slots = [MaybeUninit::<String>::uninit(), MaybeUninit::uninit()];
+/// let strings = write_clones_uninit(&mut slots, String::from("Hello"));
+/// assert_eq!(strings, ["Hello", "Hello"]);
+/// # for s in strings { unsafe { std::ptr::drop_in_place(s) } }
+/// ```
+#[cfg(feature = "unsafe-opt")]
+pub fn write_clones_uninit<C>(slots: &mut [MaybeUninit<C>], value: C) -> &mut [C]
+where
+    C: Clone,
+{
+    let n = slots.len();
+    if n == 0 {
+        return &mut [];
+    }
+    let mut guard = InitGuard {
+        slots,
+        initialized: 0,
+    };
+    while guard.initialized < n - 1 {
+        guard.slots[guard.initialized].write(value.clone());
+        guard.initialized += 1;
+    }
+    guard.slots[n - 1].write(value);
+    // Take the slots out of the guard before forgetting it, so that moving the
+    // guard does not invalidate the pointer derived from them.
+    let slots = core::mem::take(&mut guard.slots);
+    core::mem::forget(guard);
+    // SAFETY: all slots were initialized above, and `MaybeUninit<C>` has the
+    // same layout as `C`.
+    unsafe { &mut *(slots as *mut [MaybeUninit<C>] as *mut [C]) }
+}
+
+// Drops the initialized prefix of a slice of slots if initialization of the
+// remaining slots is abandoned by a panic.
+#[cfg(feature = "unsafe-opt")]
+struct InitGuard<'a, C> {
+    slots: &'a mut [MaybeUninit<C>],
+    initialized: usize,
+}
+
+#[cfg(feature = "unsafe-opt")]
+impl<'a, C> Drop for InitGuard<'a, C> {
+    fn drop(&mut self) {
+        for slot in &mut self.slots[..self.initialized] {
+            // SAFETY: slots before `initialized` have been written to.
+            unsafe { slot.assume_init_drop() };
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use crate::tests::Clonable;
+    #[cfg(feature = "unsafe-opt")]
+    use crate::write_clones_uninit;
+    use crate::{
+        fill_none_with_clones, fill_spare_with_clones, get_or_insert_clones, grid_of_clones,
+        insert_clones_at, push_clone_to_each, replace_matching,
+    };
+
+    #[test]
+    fn test_get_or_insert_clones() {
+        let count = AtomicU32::new(0);
+        let mut map = HashMap::new();
+        map.insert(2, Clonable { count: &count });
+        let cloned = Clonable { count: &count };
+        let inserted = get_or_insert_clones(&mut map, vec![1, 2, 3, 3, 4, 1], cloned);
+        assert_eq!(inserted, 3);
+        assert_eq!(map.len(), 4);
+        assert_eq!(count.load(Ordering::Relaxed), 2);
+        let cloned = Clonable { count: &count };
+        let inserted = get_or_insert_clones(&mut map, vec![5, 1, 6], cloned);
+        assert_eq!(inserted, 2);
+        assert_eq!(count.load(Ordering::Relaxed), 3);
+    }
+
+    #[test]
+    fn test_get_or_insert_clones_original_last_missing() {
+        let mut map = HashMap::new();
+        map.insert("z", String::from("existing"));
+        let value = String::from("Hello");
+        let ptr = value.as_ptr();
+        assert_eq!(get_or_insert_clones(&mut map, ["a", "b", "z"], value), 2);
+        assert_ne!(map["a"].as_ptr(), ptr);
+        assert_eq!(map["b"].as_ptr(), ptr);
+        assert_eq!(map["z"], "existing");
+    }
+
+    #[test]
+    fn test_get_or_insert_clones_none_missing() {
+        let count = AtomicU32::new(0);
+        let mut map = HashMap::new();
+        map.insert(1, Clonable { count: &count });
+        let cloned = Clonable { count: &count };
+        assert_eq!(get_or_insert_clones(&mut map, vec![1, 1], cloned), 0);
+        assert_eq!(count.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn test_fill_none_with_clones() {
+        let count = AtomicU32::new(0);
+        let mut slots = vec![None, Some(Clonable { count: &count }), None, None];
+        let cloned = Clonable { count: &count };
+        assert_eq!(fill_none_with_clones(&mut slots, cloned), 3);
+        assert!(slots.iter().all(Option::is_some));
+        assert_eq!(count.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn test_push_clone_to_each() {
+        let count = AtomicU32::new(0);
+        let mut shards = vec![Vec::new(), Vec::new(), Vec::new()];
+        assert_eq!(
+            push_clone_to_each(&mut shards, Clonable { count: &count }),
+            3
+        );
+        assert!(shards.iter().all(|shard| shard.len() == 1));
+        assert_eq!(count.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn test_push_clone_to_each_original_last() {
+        let value = String::from("event");
+        let ptr = value.as_ptr();
+        let mut shards = vec![Vec::new(); 3];
+        push_clone_to_each(&mut shards, value);
+        assert_ne!(shards[0][0].as_ptr(), ptr);
+        assert_ne!(shards[1][0].as_ptr(), ptr);
+        assert_eq!(shards[2][0].as_ptr(), ptr);
+    }
+
+    #[test]
+    fn test_insert_clones_at() {
+        let mut v = vec![1, 2, 3];
+        insert_clones_at(&mut v, &[1, 2, 4, 5], 0);
+        assert_eq!(v, [1, 0, 0, 2, 0, 0, 3]);
+        let mut v = vec![1, 2, 3];
+        insert_clones_at(&mut v, &[], 0);
+        assert_eq!(v, [1, 2, 3]);
+    }
+
+    #[test]
+    fn test_insert_clones_at_clones() {
+        let count = AtomicU32::new(0);
+        let mut v = Vec::new();
+        insert_clones_at(&mut v, &[0, 1, 2], Clonable { count: &count });
+        assert_eq!(v.len(), 3);
+        assert_eq!(count.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "strictly increasing")]
+    fn test_insert_clones_at_unsorted() {
+        insert_clones_at(&mut vec![1, 2, 3], &[2, 1], 0);
+    }
+
+    #[test]
+    fn test_replace_matching() {
+        let count = AtomicU32::new(0);
+        let mut v = vec![1, 2, 3, 4, 5, 6];
+        assert_eq!(replace_matching(&mut v, |i| i % 2 == 0, 0), 3);
+        assert_eq!(v, [1, 0, 3, 0, 5, 0]);
+        let mut v = vec![Clonable { count: &count }; 3];
+        assert_eq!(count.load(Ordering::Relaxed), 2);
+        assert_eq!(
+            replace_matching(&mut v, |_| true, Clonable { count: &count }),
+            3
+        );
+        assert_eq!(count.load(Ordering::Relaxed), 4);
+    }
+
+    #[test]
+    fn test_grid_of_clones() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let grid = grid_of_clones(3, 4, cloned);
+        assert_eq!(grid.len(), 3);
+        assert!(grid.iter().all(|row| row.len() == 4));
+        assert_eq!(count.load(Ordering::Relaxed), 11);
+    }
+
+    #[test]
+    fn test_grid_of_clones_no_columns() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let grid = grid_of_clones(3, 0, cloned);
+        assert_eq!(grid.len(), 3);
+        assert!(grid.iter().all(Vec::is_empty));
+        assert_eq!(count.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn test_fill_spare_with_clones() {
+        let count = AtomicU32::new(0);
+        let mut v = vec![Clonable { count: &count }];
+        let cloned = Clonable { count: &count };
+        fill_spare_with_clones(&mut v, 5, cloned);
+        assert_eq!(v.len(), 6);
+        assert_eq!(count.load(Ordering::Relaxed), 4);
+    }
+
+    #[test]
+    fn test_fill_spare_with_clones_panic() {
+        struct Panicky<'a> {
+            count: &'a AtomicU32,
+            drops: &'a AtomicU32,
+        }
+        impl<'a> Clone for Panicky<'a> {
+            fn clone(&self) -> Self {
+                if self.count.fetch_add(1, Ordering::Relaxed) == 2 {
+                    panic!("clone failed");
+                }
+                Panicky { ..*self }
+            }
+        }
+        impl<'a> Drop for Panicky<'a> {
+            fn drop(&mut self) {
+                self.drops.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        let count = AtomicU32::new(0);
+        let drops = AtomicU32::new(0);
+        let mut v = Vec::new();
+        let value = Panicky {
+            count: &count,
+            drops: &drops,
+        };
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            fill_spare_with_clones(&mut v, 5, value);
+        }));
+        assert!(result.is_err());
+        assert!(v.is_empty());
+        // Two successful clones and the original are dropped.
+        assert_eq!(drops.load(Ordering::Relaxed), 3);
+    }
+
+    #[cfg(feature = "unsafe-opt")]
+    #[test]
+    fn test_write_clones_uninit() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let mut slots = [(); 4].map(|_| std::mem::MaybeUninit::uninit());
+        assert_eq!(write_clones_uninit(&mut slots, cloned).len(), 4);
+        assert_eq!(count.load(Ordering::Relaxed), 3);
+    }
+
+    #[cfg(feature = "unsafe-opt")]
+    #[test]
+    fn test_write_clones_uninit_empty() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let mut slots: [std::mem::MaybeUninit<Clonable>; 0] = [];
+        assert!(write_clones_uninit(&mut slots, cloned).is_empty());
+        assert_eq!(count.load(Ordering::Relaxed), 0);
+    }
+}
@@ -0,0 +1,170 @@
+use core::iter::FusedIterator;
+
+use crate::ZipCloneIter;
+
+/// An iterator that pairs items with clones of a value, and calls a function
+/// with the leftover value when it is exhausted or dropped.
+///
+/// This struct is created by [`ZipCloneIter::on_finish`].
+pub struct OnFinish<I, C, F>
+where
+    I: Iterator,
+    F: FnOnce(Option<C>),
+{
+    inner: ZipCloneIter<I, C>,
+    f: Option<F>,
+}
+
+impl<I, C, F> OnFinish<I, C, F>
+where
+    I: Iterator,
+    F: FnOnce(Option<C>),
+{
+    pub(crate) fn new(inner: ZipCloneIter<I, C>, f: F) -> Self {
+        OnFinish { inner, f: Some(f) }
+    }
+
+    fn finish(&mut self) {
+        if let Some(f) = self.f.take() {
+            f(self.inner.cloned.take());
+        }
+    }
+}
+
+impl<I, C, F> Iterator for OnFinish<I, C, F>
+where
+    I: Iterator,
+    C: Clone,
+    F: FnOnce(Option<C>),
+{
+    type Item = (I::Item, C);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Check for the end before taking the value, so that it is kept for
+        // the function if no items remain.
+        if self.inner.iter.peek().is_none() {
+            self.finish();
+            return None;
+        }
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+
+    fn count(mut self) -> usize
+    where
+        Self: Sized,
+    {
+        // The value is left in place and passed to the function on drop.
+        if self.inner.cloned.value.is_none() {
+            return 0;
+        }
+        self.inner.iter.by_ref().count()
+    }
+
+    fn last(mut self) -> Option<Self::Item>
+    where
+        Self: Sized,
+    {
+        // With no items, the value is left in place for the function.
+        self.inner.iter.peek()?;
+        let cloned = self.inner.cloned.take()?;
+        let item = self.inner.iter.by_ref().last()?;
+        Some((item, cloned))
+    }
+}
+
+impl<I, C, F> ExactSizeIterator for OnFinish<I, C, F>
+where
+    I: ExactSizeIterator,
+    C: Clone,
+    F: FnOnce(Option<C>),
+{
+}
+
+impl<I, C, F> FusedIterator for OnFinish<I, C, F>
+where
+    I: Iterator,
+    C: Clone,
+    F: FnOnce(Option<C>),
+{
+}
+
+impl<I, C, F> Drop for OnFinish<I, C, F>
+where
+    I: Iterator,
+    F: FnOnce(Option<C>),
+{
+    fn drop(&mut self) {
+        self.finish();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use crate::tests::Clonable;
+    use crate::zip_clone;
+
+    #[test]
+    fn test_on_finish_exhausted() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let finished = Cell::new(None);
+        let mut iter = zip_clone(1..4, cloned).on_finish(|c| finished.set(Some(c.is_some())));
+        assert_eq!(iter.by_ref().map(|_| ()).count(), 3);
+        assert_eq!(finished.get(), Some(false));
+        drop(iter);
+        assert_eq!(count.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn test_on_finish_empty() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let finished = Cell::new(None);
+        let mut iter = zip_clone(0..0, cloned).on_finish(|c| finished.set(Some(c.is_some())));
+        assert!(iter.next().is_none());
+        assert_eq!(finished.get(), Some(true));
+        assert_eq!(count.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn test_on_finish_dropped() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let finished = Cell::new(None);
+        let mut iter = zip_clone(1..4, cloned).on_finish(|c| finished.set(Some(c.is_some())));
+        iter.next();
+        assert_eq!(finished.get(), None);
+        drop(iter);
+        assert_eq!(finished.get(), Some(true));
+        assert_eq!(count.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn test_on_finish_count() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let finished = Cell::new(None);
+        let iter = zip_clone(1..4, cloned).on_finish(|c| finished.set(Some(c.is_some())));
+        assert_eq!(iter.count(), 3);
+        assert_eq!(finished.get(), Some(true));
+        assert_eq!(count.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn test_on_finish_last() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let finished = Cell::new(None);
+        let iter = zip_clone(1..4, cloned).on_finish(|c| finished.set(Some(c.is_some())));
+        assert_eq!(iter.last().map(|(item, _)| item), Some(3));
+        assert_eq!(finished.get(), Some(false));
+        assert_eq!(count.load(Ordering::Relaxed), 0);
+    }
+}
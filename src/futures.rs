@@ -0,0 +1,278 @@
+//! Zip a [`Stream`] to a repeatedly cloned value.
+
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use ::futures::future::Future;
+use ::futures::ready;
+use ::futures::stream::{FusedStream, Peekable, Stream, StreamExt};
+
+/// Zip a stream to a repeatedly cloned value.
+///
+/// This is the stream equivalent of [`zip_clone`](crate::zip_clone). The last
+/// item receives the original value, so a stream of `n` items uses `n - 1`
+/// clones. To decide whether to clone, the stream waits for the following
+/// item, or the end of the stream, before returning each item.
+///
+/// Example:
+/// ```rust
+/// use futures::executor::block_on;
+/// use futures::stream::{self, StreamExt};
+/// use zip_clone::futures::zip_clone_stream;
+///
+/// let stream = zip_clone_stream(stream::iter(0..3), String::from("Hello"));
+/// let items = block_on(stream.collect::<Vec<_>>());
+/// assert_eq!(items.len(), 3);
+/// assert!(items.iter().all(|(_, s)| s == "Hello"));
+/// ```
+pub fn zip_clone_stream<S, C>(stream: S, value: C) -> ZipCloneStream<S, C>
+where
+    S: Stream + Unpin,
+    C: Clone + Unpin,
+{
+    ZipCloneStream {
+        stream: stream.peekable(),
+        pending: None,
+        value: Some(value),
+    }
+}
+
+/// A stream that pairs items with clones of a value.
+///
+/// This struct is created by [`zip_clone_stream`].
+pub struct ZipCloneStream<S, C>
+where
+    S: Stream,
+{
+    stream: Peekable<S>,
+    // An item waiting for the stream to show whether another item follows.
+    pending: Option<S::Item>,
+    value: Option<C>,
+}
+
+impl<S, C> Stream for ZipCloneStream<S, C>
+where
+    S: Stream + Unpin,
+    S::Item: Unpin,
+    C: Clone + Unpin,
+{
+    type Item = (S::Item, C);
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if this.value.is_none() {
+            return Poll::Ready(None);
+        }
+        if this.pending.is_none() {
+            match ready!(Pin::new(&mut this.stream).poll_next(cx)) {
+                Some(item) => this.pending = Some(item),
+                None => {
+                    this.value = None;
+                    return Poll::Ready(None);
+                }
+            }
+        }
+        let more = ready!(Pin::new(&mut this.stream).poll_peek(cx)).is_some();
+        let value = if more {
+            this.value.clone()
+        } else {
+            this.value.take()
+        };
+        Poll::Ready(this.pending.take().zip(value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.value.is_none() {
+            return (0, Some(0));
+        }
+        let pending = usize::from(self.pending.is_some());
+        let (lower, upper) = self.stream.size_hint();
+        (
+            lower.saturating_add(pending),
+            upper.and_then(|upper| upper.checked_add(pending)),
+        )
+    }
+}
+
+impl<S, C> FusedStream for ZipCloneStream<S, C>
+where
+    S: Stream + Unpin,
+    S::Item: Unpin,
+    C: Clone + Unpin,
+{
+    fn is_terminated(&self) -> bool {
+        self.value.is_none()
+    }
+}
+
+/// Create a stream that yields a value `n` times.
+///
+/// This is the stream equivalent of `repeat_n`, for values that implement
+/// `Clone`. The last item is the original value, so a stream of `n` items uses
+/// `n - 1` clones. If `n` is zero, the value is dropped immediately.
+///
+/// Example:
+/// ```rust
+/// use futures::executor::block_on;
+/// use futures::stream::StreamExt;
+/// use zip_clone::futures::repeat_clone;
+///
+/// let stream = repeat_clone(String::from("Hello"), 3);
+/// let items = block_on(stream.collect::<Vec<_>>());
+/// assert_eq!(items, ["Hello", "Hello", "Hello"]);
+/// ```
+pub fn repeat_clone<C>(value: C, n: usize) -> RepeatClone<C>
+where
+    C: Clone + Unpin,
+{
+    RepeatClone {
+        value: if n == 0 { None } else { Some(value) },
+        remaining: n,
+    }
+}
+
+/// A stream that yields clones of a value, and then the value itself.
+///
+/// This struct is created by [`repeat_clone`].
+pub struct RepeatClone<C> {
+    value: Option<C>,
+    remaining: usize,
+}
+
+impl<C> Stream for RepeatClone<C>
+where
+    C: Clone + Unpin,
+{
+    type Item = C;
+
+    fn poll_next(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if this.remaining <= 1 {
+            this.remaining = 0;
+            return Poll::Ready(this.value.take());
+        }
+        this.remaining -= 1;
+        Poll::Ready(this.value.clone())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<C> FusedStream for RepeatClone<C>
+where
+    C: Clone + Unpin,
+{
+    fn is_terminated(&self) -> bool {
+        self.value.is_none()
+    }
+}
+
+/// Run an async function for each item of a stream, with up to `limit`
+/// futures running at once, giving each a copy of a value.
+///
+/// Each future receives an item and a clone of the value, except the future
+/// for the last item, which receives the original. A `limit` of `None` places
+/// no limit on the number of futures running at once.
+///
+/// Example:
+/// ```rust
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use futures::executor::block_on;
+/// use futures::stream;
+/// use zip_clone::futures::for_each_concurrent_clone;
+///
+/// let total = AtomicUsize::new(0);
+/// let total_ref = &total;
+/// block_on(for_each_concurrent_clone(
+///     stream::iter(0..10),
+///     4,
+///     String::from("Hello"),
+///     |_, s| async move {
+///         total_ref.fetch_add(s.len(), Ordering::Relaxed);
+///     },
+/// ));
+/// assert_eq!(total.into_inner(), 50);
+/// ```
+pub async fn for_each_concurrent_clone<S, C, F, Fut>(
+    stream: S,
+    limit: impl Into<Option<usize>>,
+    value: C,
+    mut f: F,
+) where
+    S: Stream + Unpin,
+    S::Item: Unpin,
+    C: Clone + Unpin,
+    F: FnMut(S::Item, C) -> Fut,
+    Fut: Future<Output = ()>,
+{
+    zip_clone_stream(stream, value)
+        .for_each_concurrent(limit, |(item, value)| f(item, value))
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use ::futures::executor::block_on;
+    use ::futures::stream::{self, FusedStream, Stream, StreamExt};
+
+    use super::{for_each_concurrent_clone, repeat_clone, zip_clone_stream};
+    use crate::tests::Clonable;
+
+    #[test]
+    fn test_zip_clone_stream() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let stream = zip_clone_stream(stream::iter(1..6), cloned);
+        assert_eq!(block_on(stream.count()), 5);
+        assert_eq!(count.load(Ordering::Relaxed), 4);
+    }
+
+    #[test]
+    fn test_zip_clone_stream_empty() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let stream = zip_clone_stream(stream::iter(0..0), cloned);
+        assert_eq!(block_on(stream.count()), 0);
+        assert_eq!(count.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn test_for_each_concurrent_clone() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let items = AtomicU32::new(0);
+        let items_ref = &items;
+        block_on(for_each_concurrent_clone(
+            stream::iter(1..6),
+            2,
+            cloned,
+            |item, _| async move {
+                items_ref.fetch_add(item, Ordering::Relaxed);
+            },
+        ));
+        assert_eq!(items.load(Ordering::Relaxed), 15);
+        assert_eq!(count.load(Ordering::Relaxed), 4);
+    }
+
+    #[test]
+    fn test_repeat_clone() {
+        let count = AtomicU32::new(0);
+        let stream = repeat_clone(Clonable { count: &count }, 5);
+        assert_eq!(stream.size_hint(), (5, Some(5)));
+        assert_eq!(block_on(stream.count()), 5);
+        assert_eq!(count.load(Ordering::Relaxed), 4);
+    }
+
+    #[test]
+    fn test_repeat_clone_empty() {
+        let count = AtomicU32::new(0);
+        let mut stream = repeat_clone(Clonable { count: &count }, 0);
+        assert!(stream.is_terminated());
+        assert!(block_on(stream.next()).is_none());
+        assert_eq!(count.load(Ordering::Relaxed), 0);
+    }
+}
@@ -0,0 +1,53 @@
+//! Insert clones of a value into a
+//! [`generational_arena`](::generational_arena) arena.
+
+use ::generational_arena::{Arena, Index};
+
+use crate::ZipClone;
+
+/// Insert `n` copies of a value into an arena, returning their indices.
+///
+/// The first insertion uses the original value, so `n` insertions make `n - 1`
+/// clones.
+///
+/// Example:
+/// ```rust
+/// use generational_arena::Arena;
+/// use zip_clone::generational_arena::insert_clones;
+///
+/// let mut arena = Arena::new();
+/// let indices = insert_clones(&mut arena, 3, String::from("Hello"));
+/// assert_eq!(indices.len(), 3);
+/// assert!(indices.iter().all(|&index| arena[index] == "Hello"));
+/// ```
+pub fn insert_clones<T>(arena: &mut Arena<T>, n: usize, value: T) -> Vec<Index>
+where
+    T: Clone,
+{
+    arena.reserve(n);
+    (0..n)
+        .zip_clone(value)
+        .map(|(_, value)| arena.insert(value))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use ::generational_arena::Arena;
+
+    use super::insert_clones;
+    use crate::tests::Clonable;
+
+    #[test]
+    fn test_insert_clones() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let mut arena = Arena::new();
+        let indices = insert_clones(&mut arena, 5, cloned);
+        assert_eq!(indices.len(), 5);
+        assert!(indices.iter().all(|&index| arena.contains(index)));
+        assert_eq!(count.load(Ordering::Relaxed), 4);
+    }
+}
@@ -0,0 +1,249 @@
+//! Fill fixed-capacity [`heapless`](::heapless) collections with clones.
+//!
+//! These helpers do not allocate, so they are available without the `alloc`
+//! and `std` features. Like the rest of the crate, the last element receives
+//! the original value, so filling `n` elements makes `n - 1` clones.
+
+use ::heapless::{String, Vec};
+
+use crate::repeat_clones;
+
+/// Create a vector containing `n` copies of a value.
+///
+/// If `n` is larger than the capacity `N`, the value is returned in `Err`.
+///
+/// Example:
+/// ```rust
+/// use zip_clone::heapless::vec_of_clones;
+///
+/// let v = vec_of_clones::<_, 4>(3, [0u8; 16]).unwrap();
+/// assert_eq!(v.len(), 3);
+/// assert!(vec_of_clones::<_, 4>(5, [0u8; 16]).is_err());
+/// ```
+pub fn vec_of_clones<C, const N: usize>(n: usize, value: C) -> Result<Vec<C, N>, C>
+where
+    C: Clone,
+{
+    let mut vec = Vec::new();
+    extend_with_clones(&mut vec, n, value)?;
+    Ok(vec)
+}
+
+/// Create a vector containing `LEN` copies of a value, failing to compile if
+/// `LEN` is larger than the capacity `N`.
+///
+/// This is [`vec_of_clones`] with a compile time check.
+///
+/// Example:
+/// ```rust
+/// use zip_clone::heapless::vec_of_clones_exact;
+///
+/// let v = vec_of_clones_exact::<_, 3, 4>([0u8; 16]);
+/// assert_eq!(v.len(), 3);
+/// ```
+///
+/// A length larger than the capacity is rejected by the compiler:
+/// ```compile_fail
+/// use zip_clone::heapless::vec_of_clones_exact;
+///
+/// let v = vec_of_clones_exact::<_, 5, 4>([0u8; 16]);
+/// ```
+pub fn vec_of_clones_exact<C, const LEN: usize, const N: usize>(value: C) -> Vec<C, N>
+where
+    C: Clone,
+{
+    let () = Check::<LEN, N>::OK;
+    let mut vec = Vec::new();
+    vec.extend(repeat_clones(value, LEN));
+    vec
+}
+
+// Evaluating `OK` fails to compile if `LEN` items do not fit in a capacity of
+// `N`.
+struct Check<const LEN: usize, const N: usize>;
+
+impl<const LEN: usize, const N: usize> Check<LEN, N> {
+    const OK: () = assert!(LEN <= N, "the length is larger than the capacity");
+}
+
+/// Create a vector filled to its capacity `N` with copies of a value.
+///
+/// Example:
+/// ```rust
+/// use zip_clone::heapless::filled_vec;
+///
+/// let v = filled_vec::<_, 4>([0u8; 16]);
+/// assert!(v.is_full());
+/// ```
+pub fn filled_vec<C, const N: usize>(value: C) -> Vec<C, N>
+where
+    C: Clone,
+{
+    let mut vec = Vec::new();
+    fill_to_capacity(&mut vec, value);
+    vec
+}
+
+/// Create a string containing `n` copies of a string slice.
+///
+/// If the copies do not fit in the capacity `N`, `Err(())` is returned, as for
+/// [`String::push_str`].
+///
+/// Example:
+/// ```rust
+/// use zip_clone::heapless::string_of_copies;
+///
+/// let s = string_of_copies::<8>(3, "ab").unwrap();
+/// assert_eq!(s, "ababab");
+/// assert!(string_of_copies::<8>(5, "ab").is_err());
+/// ```
+// The unit error matches `String::push_str`.
+#[allow(clippy::result_unit_err)]
+pub fn string_of_copies<const N: usize>(n: usize, s: &str) -> Result<String<N>, ()> {
+    match s.len().checked_mul(n) {
+        Some(len) if len <= N => {}
+        _ => return Err(()),
+    }
+    let mut string = String::new();
+    for _ in 0..n {
+        string.push_str(s)?;
+    }
+    Ok(string)
+}
+
+/// Append `n` copies of a value to a vector.
+///
+/// If the vector does not have room for `n` more elements, it is left
+/// unchanged and the value is returned in `Err`.
+///
+/// Example:
+/// ```rust
+/// use heapless::Vec;
+/// use zip_clone::heapless::extend_with_clones;
+///
+/// let mut v: Vec<String, 4> = Vec::new();
+/// extend_with_clones(&mut v, 2, String::from("Hello")).unwrap();
+/// assert_eq!(v, ["Hello", "Hello"]);
+/// assert!(extend_with_clones(&mut v, 3, String::from("Hello")).is_err());
+/// ```
+pub fn extend_with_clones<C, const N: usize>(
+    vec: &mut Vec<C, N>,
+    n: usize,
+    value: C,
+) -> Result<(), C>
+where
+    C: Clone,
+{
+    if n > N - vec.len() {
+        return Err(value);
+    }
+    vec.extend(repeat_clones(value, n));
+    Ok(())
+}
+
+/// Append copies of a value to a vector until it is full.
+///
+/// Returns the number of elements added. If the vector is already full, the
+/// value is dropped without being cloned.
+///
+/// Example:
+/// ```rust
+/// use heapless::Vec;
+/// use zip_clone::heapless::fill_to_capacity;
+///
+/// let mut v: Vec<String, 4> = Vec::new();
+/// v.push(String::from("World")).unwrap();
+/// assert_eq!(fill_to_capacity(&mut v, String::from("Hello")), 3);
+/// assert_eq!(v, ["World", "Hello", "Hello", "Hello"]);
+/// ```
+pub fn fill_to_capacity<C, const N: usize>(vec: &mut Vec<C, N>, value: C) -> usize
+where
+    C: Clone,
+{
+    let n = N - vec.len();
+    vec.extend(repeat_clones(value, n));
+    n
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use ::heapless::Vec;
+
+    use super::{
+        extend_with_clones, fill_to_capacity, filled_vec, string_of_copies, vec_of_clones,
+        vec_of_clones_exact,
+    };
+    use crate::tests::Clonable;
+    use crate::ZipClone;
+
+    #[test]
+    fn test_vec_of_clones() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let v = vec_of_clones::<_, 8>(5, cloned).ok().unwrap();
+        assert_eq!(v.len(), 5);
+        assert_eq!(count.load(Ordering::Relaxed), 4);
+    }
+
+    #[test]
+    fn test_vec_of_clones_too_many() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        assert!(vec_of_clones::<_, 4>(5, cloned).is_err());
+        assert_eq!(count.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn test_vec_of_clones_exact() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let v = vec_of_clones_exact::<_, 5, 8>(cloned);
+        assert_eq!(v.len(), 5);
+        assert_eq!(count.load(Ordering::Relaxed), 4);
+    }
+
+    #[test]
+    fn test_string_of_copies() {
+        assert_eq!(string_of_copies::<6>(3, "ab").unwrap(), "ababab");
+        assert_eq!(string_of_copies::<6>(0, "ab").unwrap(), "");
+        assert!(string_of_copies::<6>(4, "ab").is_err());
+        assert!(string_of_copies::<6>(usize::MAX, "ab").is_err());
+    }
+
+    #[test]
+    fn test_filled_vec() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let v = filled_vec::<_, 6>(cloned);
+        assert!(v.is_full());
+        assert_eq!(count.load(Ordering::Relaxed), 5);
+    }
+
+    #[test]
+    fn test_extend_with_clones_unchanged_on_error() {
+        let mut v: Vec<u32, 4> = Vec::new();
+        v.extend([1, 2, 3]);
+        assert_eq!(extend_with_clones(&mut v, 2, 0), Err(0));
+        assert_eq!(v, [1, 2, 3]);
+    }
+
+    #[test]
+    fn test_fill_full_vec() {
+        let count = AtomicU32::new(0);
+        let mut v = filled_vec::<_, 2>(Clonable { count: &count });
+        count.store(0, Ordering::Relaxed);
+        assert_eq!(fill_to_capacity(&mut v, Clonable { count: &count }), 0);
+        assert_eq!(count.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn test_zip_clone_heapless_iter() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let items: Vec<u32, 4> = Vec::from_slice(&[1, 2, 3]).unwrap();
+        assert_eq!(items.into_iter().zip_clone(cloned).map(|_| ()).count(), 3);
+        assert_eq!(count.load(Ordering::Relaxed), 2);
+    }
+}
@@ -0,0 +1,107 @@
+/// Give random access to the items of a slice, paired with clones of a
+/// value.
+///
+/// [`get`](ZipCloneIndexed::get) pairs an item with a clone of the value, in
+/// any order and any number of times. For the final access, call
+/// [`take_at`](ZipCloneIndexed::take_at), which pairs the item with the
+/// original value. This suits consumers that do not visit items in sequence,
+/// such as samplers and binary searches.
+///
+/// Example:
+/// ```rust
+/// use zip_clone::zip_clone_indexed;
+///
+/// let items = [10, 20, 30, 40];
+/// let indexed = zip_clone_indexed(&items, String::from("Hello"));
+/// assert_eq!(indexed.get(2), Some((&30, String::from("Hello"))));
+/// assert_eq!(indexed.get(0), Some((&10, String::from("Hello"))));
+/// assert_eq!(indexed.take_at(3), Ok((&40, String::from("Hello"))));
+/// ```
+pub fn zip_clone_indexed<T, C>(items: &[T], value: C) -> ZipCloneIndexed<'_, T, C>
+where
+    C: Clone,
+{
+    ZipCloneIndexed { items, value }
+}
+
+/// A slice whose items can be accessed in any order, paired with clones of a
+/// value.
+///
+/// This struct is created by [`zip_clone_indexed`].
+#[derive(Debug)]
+pub struct ZipCloneIndexed<'a, T, C> {
+    items: &'a [T],
+    value: C,
+}
+
+impl<'a, T, C> ZipCloneIndexed<'a, T, C>
+where
+    C: Clone,
+{
+    /// Return the number of items.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Return `true` if there are no items.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Return the item at an index, paired with a clone of the value, or
+    /// `None` if the index is out of bounds.
+    ///
+    /// No clone is made for an index that is out of bounds.
+    pub fn get(&self, index: usize) -> Option<(&'a T, C)> {
+        let item = self.items.get(index)?;
+        Some((item, self.value.clone()))
+    }
+
+    /// Return the item at an index, paired with the original value, ending
+    /// access to the items.
+    ///
+    /// If the index is out of bounds, the value is returned as the error, so
+    /// it is not lost.
+    pub fn take_at(self, index: usize) -> Result<(&'a T, C), C> {
+        match self.items.get(index) {
+            Some(item) => Ok((item, self.value)),
+            None => Err(self.value),
+        }
+    }
+
+    /// Return the value, ending access to the items.
+    pub fn into_value(self) -> C {
+        self.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use crate::tests::Clonable;
+    use crate::zip_clone_indexed;
+
+    #[test]
+    fn test_zip_clone_indexed() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let items = [1, 2, 3, 4, 5];
+        let indexed = zip_clone_indexed(&items, cloned);
+        assert_eq!(indexed.len(), 5);
+        assert_eq!(indexed.get(4).map(|(item, _)| *item), Some(5));
+        assert!(indexed.get(5).is_none());
+        assert_eq!(indexed.get(1).map(|(item, _)| *item), Some(2));
+        assert_eq!(indexed.take_at(2).map(|(item, _)| *item).ok(), Some(3));
+        assert_eq!(count.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn test_zip_clone_indexed_out_of_bounds() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let indexed = zip_clone_indexed(&[1, 2], cloned);
+        assert!(indexed.take_at(2).is_err());
+        assert_eq!(count.load(Ordering::Relaxed), 0);
+    }
+}
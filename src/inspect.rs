@@ -0,0 +1,182 @@
+use core::iter::FusedIterator;
+
+/// Call a function with a reference to each pair returned by an iterator of
+/// pairs, such as a [`ZipCloneIter`](crate::ZipCloneIter).
+///
+/// Unlike [`Iterator::inspect`], the adapter forwards `count`, `last`, `nth`
+/// and `find` to the wrapped iterator, so it keeps their clone-saving
+/// behavior. The function is only called for pairs that the wrapped iterator
+/// creates. For example, `count` calls it for no pairs, and `last` calls it
+/// for the last pair only.
+///
+/// Example:
+/// ```rust
+/// use zip_clone::{inspect_pair, ZipClone};
+///
+/// let mut seen = Vec::new();
+/// let iter = inspect_pair((0..3).zip_clone(String::from("Hello")), |i, s| {
+///     seen.push(format!("{} {}", i, s));
+/// });
+/// assert_eq!(iter.count(), 3);
+/// assert!(seen.is_empty());
+/// ```
+pub fn inspect_pair<J, T, C, F>(iter: J, f: F) -> InspectPair<J, F>
+where
+    J: Iterator<Item = (T, C)>,
+    F: FnMut(&T, &C),
+{
+    InspectPair { iter, f }
+}
+
+/// An iterator that calls a function with a reference to each pair before
+/// returning it.
+///
+/// This struct is created by [`inspect_pair`] and
+/// [`ZipCloneIter::inspect_pair`](crate::ZipCloneIter::inspect_pair).
+pub struct InspectPair<J, F> {
+    iter: J,
+    f: F,
+}
+
+impl<J, F> InspectPair<J, F> {
+    fn inspect<T, C>(&mut self, pair: Option<(T, C)>) -> Option<(T, C)>
+    where
+        F: FnMut(&T, &C),
+    {
+        if let Some((item, cloned)) = &pair {
+            (self.f)(item, cloned);
+        }
+        pair
+    }
+}
+
+impl<J, T, C, F> Iterator for InspectPair<J, F>
+where
+    J: Iterator<Item = (T, C)>,
+    F: FnMut(&T, &C),
+{
+    type Item = (T, C);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let pair = self.iter.next();
+        self.inspect(pair)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    fn count(self) -> usize
+    where
+        Self: Sized,
+    {
+        self.iter.count()
+    }
+
+    fn last(mut self) -> Option<Self::Item>
+    where
+        Self: Sized,
+    {
+        let pair = self.iter.last();
+        if let Some((item, cloned)) = &pair {
+            (self.f)(item, cloned);
+        }
+        pair
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let pair = self.iter.nth(n);
+        self.inspect(pair)
+    }
+
+    fn find<P>(&mut self, mut predicate: P) -> Option<Self::Item>
+    where
+        Self: Sized,
+        P: FnMut(&Self::Item) -> bool,
+    {
+        let f = &mut self.f;
+        self.iter.find(|pair| {
+            f(&pair.0, &pair.1);
+            predicate(pair)
+        })
+    }
+}
+
+impl<J, T, C, F> DoubleEndedIterator for InspectPair<J, F>
+where
+    J: DoubleEndedIterator<Item = (T, C)>,
+    F: FnMut(&T, &C),
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let pair = self.iter.next_back();
+        self.inspect(pair)
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        let pair = self.iter.nth_back(n);
+        self.inspect(pair)
+    }
+}
+
+impl<J, T, C, F> ExactSizeIterator for InspectPair<J, F>
+where
+    J: ExactSizeIterator<Item = (T, C)>,
+    F: FnMut(&T, &C),
+{
+}
+
+impl<J, T, C, F> FusedIterator for InspectPair<J, F>
+where
+    J: FusedIterator<Item = (T, C)>,
+    F: FnMut(&T, &C),
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use crate::tests::Clonable;
+    use crate::{inspect_pair, zip_clone};
+
+    #[test]
+    fn test_inspect_pair() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let mut seen = Vec::new();
+        let n = inspect_pair(zip_clone(1..6, cloned), |&i, _| seen.push(i))
+            .map(|_| ())
+            .count();
+        assert_eq!(n, 5);
+        assert_eq!(seen, [1, 2, 3, 4, 5]);
+        assert_eq!(count.load(Ordering::Relaxed), 4);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "predictable", ignore)]
+    fn test_inspect_pair_keeps_overrides() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let mut seen = Vec::new();
+        let last = zip_clone(1..6, cloned)
+            .inspect_pair(|&i, _| seen.push(i))
+            .last();
+        assert_eq!(last.unwrap().0, 5);
+        assert_eq!(seen, [5]);
+        assert_eq!(count.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "predictable", ignore)]
+    fn test_inspect_pair_find() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let mut seen = Vec::new();
+        let found = zip_clone(1..6, cloned)
+            .inspect_pair(|&i, _| seen.push(i))
+            .find(|(i, _)| *i == 3);
+        assert_eq!(found.unwrap().0, 3);
+        assert_eq!(seen, [1, 2, 3]);
+        assert_eq!(count.load(Ordering::Relaxed), 1);
+    }
+}
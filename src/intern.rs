@@ -0,0 +1,223 @@
+use core::fmt;
+use core::hash::{BuildHasher, Hash, Hasher};
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+use std::collections::hash_map::{HashMap, RandomState};
+
+/// A handle to a value stored in an [`Interner`].
+///
+/// Handles are `Copy`, so pairing a handle with every item costs nothing.
+/// Two handles from the same interner are equal if and only if their values
+/// are equal. A handle must only be resolved by the interner that created it.
+pub struct Intern<C> {
+    index: usize,
+    marker: PhantomData<fn() -> C>,
+}
+
+impl<C> Clone for Intern<C> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C> Copy for Intern<C> {}
+
+impl<C> PartialEq for Intern<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index
+    }
+}
+
+impl<C> Eq for Intern<C> {}
+
+impl<C> Hash for Intern<C> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.index.hash(state);
+    }
+}
+
+impl<C> fmt::Debug for Intern<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Intern").field(&self.index).finish()
+    }
+}
+
+/// A store of distinct values, that pairs items with handles to the values
+/// instead of clones.
+///
+/// Interning a value that is equal to a stored value drops the new value and
+/// returns the handle of the stored value, so each distinct value is stored
+/// once. No value is ever cloned.
+///
+/// Example:
+/// ```rust
+/// use zip_clone::Interner;
+///
+/// let mut interner = Interner::new();
+/// let a = interner.zip(0..1000, String::from("a")).collect::<Vec<_>>();
+/// let b = interner.zip(0..1000, String::from("a")).collect::<Vec<_>>();
+/// assert_eq!(a[0].1, b[999].1);
+/// assert_eq!(interner.resolve(a[0].1), "a");
+/// assert_eq!(interner.len(), 1);
+/// ```
+pub struct Interner<C, S = RandomState> {
+    values: Vec<C>,
+    // Indexes of the stored values, by the hash of the value.
+    buckets: HashMap<u64, Vec<usize>>,
+    hasher: S,
+}
+
+impl<C> Interner<C, RandomState> {
+    /// Create an empty interner.
+    pub fn new() -> Self {
+        Interner::with_hasher(RandomState::new())
+    }
+}
+
+impl<C> Default for Interner<C, RandomState> {
+    fn default() -> Self {
+        Interner::new()
+    }
+}
+
+impl<C, S> Interner<C, S> {
+    /// Create an empty interner, using `hasher` to hash values.
+    pub fn with_hasher(hasher: S) -> Self {
+        Interner {
+            values: Vec::new(),
+            buckets: HashMap::new(),
+            hasher,
+        }
+    }
+
+    /// Return the number of distinct values.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Return `true` if no values have been interned.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Return a reference to the value for a handle.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the handle was created by a different interner holding fewer
+    /// values.
+    pub fn resolve(&self, handle: Intern<C>) -> &C {
+        &self.values[handle.index]
+    }
+
+    /// Return the distinct values, in the order they were first interned.
+    pub fn into_values(self) -> Vec<C> {
+        self.values
+    }
+}
+
+impl<C, S> Interner<C, S>
+where
+    C: Eq + Hash,
+    S: BuildHasher,
+{
+    /// Store a value, returning a handle to it, or to an equal value that was
+    /// already stored.
+    pub fn intern(&mut self, value: C) -> Intern<C> {
+        let mut state = self.hasher.build_hasher();
+        value.hash(&mut state);
+        let bucket = self.buckets.entry(state.finish()).or_default();
+        let values = &mut self.values;
+        let index = match bucket.iter().find(|&&index| values[index] == value) {
+            Some(&index) => index,
+            None => {
+                values.push(value);
+                bucket.push(values.len() - 1);
+                values.len() - 1
+            }
+        };
+        Intern {
+            index,
+            marker: PhantomData,
+        }
+    }
+
+    /// Zip an iterator to a handle to a value.
+    ///
+    /// The value is interned once, and every item is paired with a copy of the
+    /// handle.
+    pub fn zip<I>(&mut self, iter: I, value: C) -> ZipInterned<I::IntoIter, C>
+    where
+        I: IntoIterator,
+    {
+        ZipInterned {
+            iter: iter.into_iter(),
+            handle: self.intern(value),
+        }
+    }
+}
+
+/// An iterator that pairs items with a handle to an interned value.
+///
+/// This struct is created by [`Interner::zip`].
+pub struct ZipInterned<I, C> {
+    iter: I,
+    handle: Intern<C>,
+}
+
+impl<I, C> Iterator for ZipInterned<I, C>
+where
+    I: Iterator,
+{
+    type Item = (I::Item, Intern<C>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.iter.next()?;
+        Some((item, self.handle))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I, C> DoubleEndedIterator for ZipInterned<I, C>
+where
+    I: DoubleEndedIterator,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let item = self.iter.next_back()?;
+        Some((item, self.handle))
+    }
+}
+
+impl<I, C> ExactSizeIterator for ZipInterned<I, C> where I: ExactSizeIterator {}
+
+impl<I, C> FusedIterator for ZipInterned<I, C> where I: FusedIterator {}
+
+#[cfg(test)]
+mod tests {
+    use crate::Interner;
+
+    #[test]
+    fn test_interner() {
+        let mut interner = Interner::new();
+        let a = interner.intern(String::from("a"));
+        let b = interner.intern(String::from("b"));
+        assert_ne!(a, b);
+        assert_eq!(interner.intern(String::from("a")), a);
+        assert_eq!(interner.resolve(b), "b");
+        assert_eq!(interner.into_values(), ["a", "b"]);
+    }
+
+    #[test]
+    fn test_interner_zip() {
+        let mut interner = Interner::new();
+        let pairs = interner
+            .zip(0..3, vec![1, 2])
+            .chain(interner.zip(3..6, vec![1, 2]))
+            .collect::<Vec<_>>();
+        assert!(pairs.iter().all(|(_, handle)| *handle == pairs[0].1));
+        assert_eq!(interner.len(), 1);
+    }
+}
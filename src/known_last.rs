@@ -0,0 +1,351 @@
+use core::iter::{Cloned, Copied, Enumerate, FusedIterator, Map, Rev, Take};
+use core::ops::Range;
+use core::slice;
+
+/// An iterator that can report that it has no more items without advancing.
+///
+/// [`zip_clone_known_last`] uses this instead of a `Peekable` lookahead to
+/// find the last item. Implement it for an iterator that knows its remaining
+/// length to make zipping it lookahead-free. [`zip_clone`](crate::zip_clone)
+/// cannot choose this automatically, since that would need specialization,
+/// so call `zip_clone_known_last` directly.
+///
+/// The implementations for adapters such as `Take` and `Map` rely on an upper
+/// bound of zero from `size_hint`, which the adapters report when the
+/// underlying iterator does. Returning `false` when no items remain is
+/// allowed, and costs one unnecessary clone. Returning `true` when items
+/// remain is a logic error, and causes those items to be dropped.
+pub trait KnownLast: Iterator {
+    /// Return `true` if the next call to `next` will return `None`.
+    fn is_finished(&self) -> bool;
+}
+
+macro_rules! impl_known_last_range {
+    ($($t:ty),*) => {
+        $(
+            impl KnownLast for Range<$t> {
+                fn is_finished(&self) -> bool {
+                    self.start >= self.end
+                }
+            }
+        )*
+    };
+}
+
+impl_known_last_range!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+impl<T> KnownLast for slice::Iter<'_, T> {
+    fn is_finished(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T> KnownLast for slice::IterMut<'_, T> {
+    fn is_finished(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> KnownLast for alloc::vec::IntoIter<T> {
+    fn is_finished(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<I> KnownLast for Take<I>
+where
+    I: KnownLast,
+{
+    fn is_finished(&self) -> bool {
+        self.size_hint().1 == Some(0)
+    }
+}
+
+impl<I> KnownLast for Rev<I>
+where
+    I: KnownLast + DoubleEndedIterator,
+{
+    fn is_finished(&self) -> bool {
+        self.size_hint().1 == Some(0)
+    }
+}
+
+impl<I> KnownLast for Enumerate<I>
+where
+    I: KnownLast,
+{
+    fn is_finished(&self) -> bool {
+        self.size_hint().1 == Some(0)
+    }
+}
+
+impl<B, I, F> KnownLast for Map<I, F>
+where
+    I: KnownLast,
+    F: FnMut(I::Item) -> B,
+{
+    fn is_finished(&self) -> bool {
+        self.size_hint().1 == Some(0)
+    }
+}
+
+impl<'a, T, I> KnownLast for Copied<I>
+where
+    T: 'a + Copy,
+    I: KnownLast<Item = &'a T>,
+{
+    fn is_finished(&self) -> bool {
+        self.size_hint().1 == Some(0)
+    }
+}
+
+impl<'a, T, I> KnownLast for Cloned<I>
+where
+    T: 'a + Clone,
+    I: KnownLast<Item = &'a T>,
+{
+    fn is_finished(&self) -> bool {
+        self.size_hint().1 == Some(0)
+    }
+}
+
+/// A wrapper that implements [`KnownLast`] for any `ExactSizeIterator`, using
+/// its length.
+///
+/// Example:
+/// ```rust
+/// use zip_clone::{zip_clone_known_last, ByLen};
+///
+/// let words = "a b c".split(' ').collect::<Vec<_>>();
+/// let iter = zip_clone_known_last(ByLen(words.iter().skip(1)), String::from("Hello"));
+/// assert_eq!(iter.count(), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ByLen<I>(pub I);
+
+impl<I> Iterator for ByLen<I>
+where
+    I: ExactSizeIterator,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<I> DoubleEndedIterator for ByLen<I>
+where
+    I: ExactSizeIterator + DoubleEndedIterator,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back()
+    }
+}
+
+impl<I> ExactSizeIterator for ByLen<I> where I: ExactSizeIterator {}
+
+impl<I> KnownLast for ByLen<I>
+where
+    I: ExactSizeIterator,
+{
+    fn is_finished(&self) -> bool {
+        self.0.len() == 0
+    }
+}
+
+/// Zip an iterator that implements [`KnownLast`] to a repeatedly cloned
+/// value, without a lookahead.
+///
+/// This is [`zip_clone`](crate::zip_clone), but asks the iterator whether an
+/// item is the last, instead of taking the following item early. The
+/// iterator is never advanced beyond the item being returned.
+///
+/// Example:
+/// ```rust
+/// use zip_clone::zip_clone_known_last;
+///
+/// let items = [1, 2, 3];
+/// let mut iter = zip_clone_known_last(items.iter(), String::from("Hello"));
+/// assert_eq!(iter.next(), Some((&1, String::from("Hello"))));
+/// assert_eq!(iter.into_inner().as_slice(), [2, 3]);
+/// ```
+pub fn zip_clone_known_last<I, C>(iter: I, value: C) -> ZipCloneKnownLast<I, C>
+where
+    I: KnownLast,
+    C: Clone,
+{
+    ZipCloneKnownLast {
+        iter,
+        value: Some(value),
+    }
+}
+
+/// Zip an iterator to a repeatedly cloned value, collecting all the items
+/// first to learn their number.
+///
+/// This trades memory for certainty about the length. The result has an
+/// exact length and is double-ended even if the source is neither, the clones
+/// are made only as items are returned, and no item is taken from the source
+/// after the first item is returned. Prefer this when clones are much more
+/// expensive than storing the items, and the source does not report a useful
+/// `size_hint`.
+///
+/// Example:
+/// ```rust
+/// use zip_clone::zip_clone_buffered;
+///
+/// let lines = "a\nb\nc".lines().filter(|line| !line.is_empty());
+/// let iter = zip_clone_buffered(lines, String::from("Hello"));
+/// assert_eq!(iter.len(), 3);
+/// assert_eq!(iter.rev().next(), Some(("c", String::from("Hello"))));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn zip_clone_buffered<I, C>(
+    iter: I,
+    value: C,
+) -> ZipCloneKnownLast<alloc::vec::IntoIter<I::Item>, C>
+where
+    I: IntoIterator,
+    C: Clone,
+{
+    let items = iter.into_iter().collect::<alloc::vec::Vec<_>>();
+    zip_clone_known_last(items.into_iter(), value)
+}
+
+/// An iterator that pairs items with clones of a value, using [`KnownLast`]
+/// to find the last item.
+///
+/// This struct is created by [`zip_clone_known_last`].
+pub struct ZipCloneKnownLast<I, C> {
+    iter: I,
+    value: Option<C>,
+}
+
+impl<I, C> ZipCloneKnownLast<I, C> {
+    /// Return the underlying iterator, which has not been advanced beyond the
+    /// items already returned.
+    pub fn into_inner(self) -> I {
+        self.iter
+    }
+}
+
+impl<I, C> ZipCloneKnownLast<I, C>
+where
+    I: KnownLast,
+    C: Clone,
+{
+    fn pair(&mut self, item: I::Item) -> Option<(I::Item, C)> {
+        let value = if self.iter.is_finished() {
+            self.value.take()?
+        } else {
+            self.value.clone()?
+        };
+        Some((item, value))
+    }
+}
+
+impl<I, C> Iterator for ZipCloneKnownLast<I, C>
+where
+    I: KnownLast,
+    C: Clone,
+{
+    type Item = (I::Item, C);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.value.as_ref()?;
+        let item = self.iter.next()?;
+        self.pair(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.value.is_none() {
+            return (0, Some(0));
+        }
+        self.iter.size_hint()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.value.as_ref()?;
+        let item = self.iter.nth(n)?;
+        self.pair(item)
+    }
+}
+
+impl<I, C> DoubleEndedIterator for ZipCloneKnownLast<I, C>
+where
+    I: KnownLast + DoubleEndedIterator,
+    C: Clone,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.value.as_ref()?;
+        let item = self.iter.next_back()?;
+        self.pair(item)
+    }
+}
+
+impl<I, C> ExactSizeIterator for ZipCloneKnownLast<I, C>
+where
+    I: KnownLast + ExactSizeIterator,
+    C: Clone,
+{
+}
+
+// A conservative `is_finished` keeps the value after the last item, so the
+// adapter is only fused if the underlying iterator is.
+impl<I, C> FusedIterator for ZipCloneKnownLast<I, C>
+where
+    I: KnownLast + FusedIterator,
+    C: Clone,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use crate::tests::Clonable;
+    use crate::{zip_clone_known_last, ByLen};
+
+    #[test]
+    fn test_zip_clone_known_last() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let iter = zip_clone_known_last((0..10).rev().enumerate().take(5), cloned);
+        assert_eq!(iter.len(), 5);
+        assert_eq!(
+            iter.map(|((_, i), _)| i).collect::<Vec<_>>(),
+            [9, 8, 7, 6, 5]
+        );
+        assert_eq!(count.load(Ordering::Relaxed), 4);
+    }
+
+    #[test]
+    fn test_zip_clone_known_last_no_lookahead() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let mut iter = zip_clone_known_last(ByLen((0..5).inspect(|i| assert!(*i < 2))), cloned);
+        assert_eq!(iter.next().map(|(i, _)| i), Some(0));
+        assert_eq!(iter.next().map(|(i, _)| i), Some(1));
+        assert_eq!(iter.into_inner().len(), 3);
+        assert_eq!(count.load(Ordering::Relaxed), 2);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_zip_clone_buffered() {
+        use crate::zip_clone_buffered;
+
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let iter = zip_clone_buffered((0..10).filter(|i| i % 3 == 0), cloned);
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.map(|(i, _)| i).collect::<Vec<_>>(), [0, 3, 6, 9]);
+        assert_eq!(count.load(Ordering::Relaxed), 3);
+    }
+}
@@ -16,12 +16,243 @@
 //!     assert_eq!(s, String::from("Hello"));
 //! }
 //! ```
+//!
+//! Every function returning an iterator returns a named struct rather than an
+//! `impl Iterator`, so adapters can be stored in struct fields and named in
+//! trait bounds.
+//!
+//! # Features
+//!
+//! The crate is `no_std` when the default `std` feature is disabled. The core
+//! adapters only need `core`. Helpers that allocate need the `alloc` feature,
+//! and helpers that use threads or hash maps need the `std` feature.
+//! `par_chunks_for_each_clone` and `SharedZipClone` also need Rust 1.63 or
+//! later, for `std::thread::scope`.
+//!
+//! `zip_shared` shares a value between items through a reference-counted
+//! pointer rather than cloning it. It works with `Rc` and `Arc`, and with
+//! `triomphe::Arc` when the `triomphe` feature is enabled.
+//!
+//! The `nightly` feature requires a nightly compiler and overrides unstable
+//! `Iterator` methods, such as `try_find`, to avoid unnecessary clones.
+//!
+//! The default build contains no unsafe code. The `unsafe-opt` feature
+//! enables fast paths that use unsafe code, such as writing clones directly
+//! into uninitialized memory, and the functions that expose them, such as
+//! `write_clones_uninit`. Its tests are run under Miri with
+//! `cargo +nightly miri test --features unsafe-opt`.
+//!
+//! The `defmt` feature logs clones, values moved into the last item, and
+//! unused values that are dropped, using `defmt` at trace level. It does not
+//! need `std`.
+//!
+//! The `predictable` feature makes clones at the same points as a plain loop
+//! over `next`, for values whose `Clone` has side effects such as allocating
+//! IDs. It disables the overrides of `count`, `last`, `find`, `nth` and the
+//! `max` and `min` methods that skip clones, and the fast path for zero-sized
+//! values.
+//!
+//! The `trace` feature records the operations performed on a `ZipCloneIter`,
+//! and the clones and moves of its value, for inspection with `trace`.
+//!
+//! The `macros` feature provides the `#[minimize_clones]` attribute, which
+//! rewrites loops that clone a value on every iteration to use `zip_clone`.
+//!
+//! # Panics
+//!
+//! The iterator methods of `ZipCloneIter`, and the fill, pad and exact helpers,
+//! do not panic unless the value's `Clone` implementation or the underlying
+//! iterator panics. This is checked by `cargo test --release --test no_panic`,
+//! which fails to link if the optimized code contains a path that can panic.
+//! The check needs `--release`: in a debug build the test is empty. Helpers
+//! that allocate, such as `grid_of_clones`, may also panic if allocation
+//! fails.
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+#![cfg_attr(not(feature = "unsafe-opt"), forbid(unsafe_code))]
+#![cfg_attr(
+    all(feature = "nightly", not(feature = "predictable")),
+    feature(try_find, try_trait_v2, try_trait_v2_residual)
+)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "arrow")]
+pub mod arrow;
+// The `dashmap` dependency itself needs Rust 1.65.
+#[cfg(feature = "dashmap")]
+#[clippy::msrv = "1.65"]
+pub mod dashmap;
+#[cfg(feature = "either")]
+pub mod either;
+pub mod error;
+#[cfg(feature = "futures")]
+pub mod futures;
+#[cfg(feature = "generational-arena")]
+pub mod generational_arena;
+#[cfg(feature = "heapless")]
+pub mod heapless;
+#[cfg(feature = "ndarray")]
+pub mod ndarray;
+#[cfg(feature = "rand")]
+pub mod rand;
+#[cfg(feature = "rayon")]
+pub mod rayon;
+#[cfg(feature = "serde")]
+pub mod serde;
+#[cfg(feature = "slotmap")]
+pub mod slotmap;
+#[cfg(feature = "testing")]
+pub mod testing;
+#[cfg(feature = "tokio")]
+pub mod tokio;
+
+#[cfg(feature = "std")]
+mod background;
+#[cfg(feature = "alloc")]
+mod batched;
+mod bounded;
+mod chain;
+#[cfg(feature = "alloc")]
+mod collect;
+#[cfg(feature = "alloc")]
+mod cow;
+#[cfg(feature = "std")]
+mod cow_map;
+mod delegate;
+mod duplicate;
+mod exact;
+mod fill;
+mod finish;
+mod indexed;
+mod inspect;
+#[cfg(feature = "std")]
+mod intern;
+mod known_last;
+#[cfg(feature = "std")]
+mod lines;
+mod lookahead;
+mod lossy;
+mod map_while;
+#[cfg(feature = "alloc")]
+mod one_or_many;
+mod pad;
+mod patched;
+mod position;
+mod prototype;
+mod repeat_slice;
+#[cfg(feature = "alloc")]
+mod repeat_vec;
+#[cfg(feature = "alloc")]
+mod round_robin;
+#[cfg(feature = "alloc")]
+mod select;
+#[cfg(feature = "alloc")]
+mod shared;
+mod slots;
+#[cfg(feature = "alloc")]
+mod template;
+#[cfg(all(feature = "std", has_thread_scope))]
+#[clippy::msrv = "1.63"]
+mod threads;
+#[cfg(feature = "trace")]
+mod trace;
+#[cfg(feature = "alloc")]
+mod try_clone;
+mod try_map;
+mod values;
+
+/// The error returned by [`TryClone::try_clone`], re-exported so that it can
+/// be named without `std`.
+#[cfg(feature = "alloc")]
+pub use alloc::collections::TryReserveError;
+#[cfg(feature = "std")]
+pub use background::{zip_clone_background, ZipCloneBackground};
+#[cfg(feature = "alloc")]
+pub use batched::{zip_clone_batched, ZipCloneBatched};
+pub use bounded::CloneBudget;
+pub use chain::ChainItems;
+#[cfg(feature = "alloc")]
+pub use collect::collect_reusing;
+#[cfg(feature = "alloc")]
+pub use cow::{zip_cow, zip_static_clone, ZipCowIter};
+#[cfg(feature = "std")]
+pub use cow_map::CowMap;
+pub use duplicate::{
+    zip_duplicate, ByClone, ByDistribute, ByFactory, BySplit, DistributeOwnership, Duplicate,
+};
+#[cfg(feature = "alloc")]
+pub use duplicate::{zip_split_weighted, ByTryClone, ZipSplitWeighted};
+pub use exact::zip_clone_exact;
+#[cfg(feature = "std")]
+pub use fill::get_or_insert_clones;
+#[cfg(feature = "unsafe-opt")]
+pub use fill::write_clones_uninit;
+pub use fill::{fill_none_with_clones, replace_matching};
+#[cfg(feature = "alloc")]
+pub use fill::{
+    fill_spare_with_clones, flat_grid_of_clones, grid_of_clones, insert_clones_at,
+    push_clone_to_each,
+};
+pub use finish::OnFinish;
+pub use indexed::{zip_clone_indexed, ZipCloneIndexed};
+pub use inspect::{inspect_pair, InspectPair};
+#[cfg(feature = "std")]
+pub use intern::{Intern, Interner, ZipInterned};
+#[cfg(feature = "alloc")]
+pub use known_last::zip_clone_buffered;
+pub use known_last::{zip_clone_known_last, ByLen, KnownLast, ZipCloneKnownLast};
+#[cfg(feature = "std")]
+pub use lines::for_each_line_with;
+pub use lossy::{zip_clone_lossy, ZipCloneLossy};
+pub use map_while::{map_while_clone, MapWhileClone};
+#[cfg(feature = "alloc")]
+pub use one_or_many::{map_one_or_many, OneOrMany};
+pub use pad::{pad_with_clones, PadWithClones};
+pub use patched::{zip_patched, Patch, ZipPatched};
+pub use position::{with_position_clone, Position, WithPositionClone};
+pub use prototype::Prototype;
+pub use repeat_slice::RepeatSlice;
+#[cfg(feature = "alloc")]
+pub use repeat_vec::{RepeatVec, RepeatVecIter};
+#[cfg(feature = "alloc")]
+pub use round_robin::{round_robin_clones, RoundRobinClones};
+#[cfg(feature = "alloc")]
+pub use select::{zip_clone_original_by_key, ZipCloneOriginalByKey};
+#[cfg(feature = "alloc")]
+pub use shared::{
+    zip_share_or_clone, zip_share_or_clone_with_threshold, zip_shared, ShareOrClone, SharedPointer,
+    ZipShareOrClone, DEFAULT_SHARE_THRESHOLD,
+};
+#[cfg(feature = "std")]
+pub use shared::{zip_share_or_clone_adaptive, ZipShareOrCloneAdaptive};
+pub use slots::{fill_from_prototype, Slot, Slots};
+#[cfg(feature = "alloc")]
+pub use template::{RenderEach, TemplateString};
+#[cfg(all(feature = "std", has_thread_scope))]
+pub use threads::{par_chunks_for_each_clone, SharedZipClone};
+#[cfg(feature = "trace")]
+pub use trace::Operation;
+#[cfg(feature = "alloc")]
+pub use try_clone::{try_zip_clone, TryClone, TryZipClone};
+pub use try_map::{try_map_clone, TryMapClone};
+pub use values::IntoValues;
+#[cfg(feature = "macros")]
+pub use zip_clone_macros::{minimize_clones, DistributeOwnership, TryClone};
+
+use lookahead::Lookahead;
 
 /// Zip an iterator to a repeately cloned object.
 ///
 /// One iteration returns the original object, thus using one fewer clones than
 /// the otherwise equivalent `iter.zip(repeat_with(|| cloned.clone()))`.
 ///
+/// Zero-sized objects are cheap to clone, so for them the iterator does not
+/// peek at the next item, and every item is returned with a clone. The
+/// adapter still has the same fields as for other values, so it is a little
+/// larger than a plain `map` over the iterator.
+///
 /// Example:
 /// ```rust
 /// use zip_clone::zip_clone;
@@ -38,16 +269,120 @@ where
     C: Clone,
 {
     ZipCloneIter {
-        iter: iter.peekable(),
-        cloned: Some(cloned),
+        iter: Lookahead::new(iter.peekable()),
+        cloned: Held::new(cloned),
+        strategy: ByClone,
     }
 }
 
+// Return `n` copies of a value, using one fewer clones than copies.
+#[cfg(all(has_repeat_n, any(feature = "alloc", feature = "heapless")))]
+#[clippy::msrv = "1.82"]
+pub(crate) fn repeat_clones<C>(value: C, n: usize) -> impl ExactSizeIterator<Item = C>
+where
+    C: Clone,
+{
+    core::iter::repeat_n(value, n)
+}
+
+#[cfg(all(not(has_repeat_n), any(feature = "alloc", feature = "heapless")))]
+pub(crate) fn repeat_clones<C>(value: C, n: usize) -> impl ExactSizeIterator<Item = C>
+where
+    C: Clone,
+{
+    RepeatClones {
+        value: Some(value),
+        remaining: n,
+    }
+}
+
+// Yields clones of a value, then the value itself, like `repeat_n`.
+#[cfg(all(not(has_repeat_n), any(feature = "alloc", feature = "heapless")))]
+struct RepeatClones<C> {
+    value: Option<C>,
+    remaining: usize,
+}
+
+#[cfg(all(not(has_repeat_n), any(feature = "alloc", feature = "heapless")))]
+impl<C> Iterator for RepeatClones<C>
+where
+    C: Clone,
+{
+    type Item = C;
+
+    fn next(&mut self) -> Option<C> {
+        match self.remaining {
+            0 => None,
+            1 => {
+                self.remaining = 0;
+                self.value.take()
+            }
+            _ => {
+                self.remaining -= 1;
+                self.value.clone()
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+#[cfg(all(not(has_repeat_n), any(feature = "alloc", feature = "heapless")))]
+impl<C> ExactSizeIterator for RepeatClones<C> where C: Clone {}
+
 /// Trait to zip an iterator to a repeately cloned object.
-pub trait ZipClone: Iterator + Sized {
+///
+/// This trait is implemented for all iterators and is sealed, so it cannot be
+/// implemented outside this crate. This allows methods to be added to it
+/// without breaking compatibility.
+pub trait ZipClone: Iterator + Sized + sealed::Sealed {
     fn zip_clone<C>(self, cloned: C) -> ZipCloneIter<Self, C>
     where
         C: Clone;
+
+    #[cfg(feature = "alloc")]
+    fn zip_cow<'a, B>(self, cow: alloc::borrow::Cow<'a, B>) -> ZipCowIter<'a, Self, B>
+    where
+        B: alloc::borrow::ToOwned + ?Sized;
+
+    #[cfg(feature = "alloc")]
+    fn zip_shared<P>(self, value: P::Target) -> ZipCloneIter<Self, P>
+    where
+        P: SharedPointer;
+
+    #[cfg(feature = "alloc")]
+    fn map_one_or_many<C, R, F>(self, value: C, f: F) -> OneOrMany<R>
+    where
+        C: Clone,
+        F: FnMut(Self::Item, C) -> R;
+
+    /// Alias for [`ZipClone::zip_clone`], named after rayon's `map_with` and
+    /// `for_each_with`.
+    ///
+    /// Example:
+    /// ```rust
+    /// use zip_clone::ZipClone;
+    ///
+    /// let s = String::from("Hello");
+    /// let iter = 0..10;
+    /// for (i, s) in iter.with_cloned(s) {
+    ///     assert_eq!(s, String::from("Hello"));
+    /// }
+    /// ```
+    fn with_cloned<C>(self, cloned: C) -> ZipCloneIter<Self, C>
+    where
+        C: Clone,
+    {
+        self.zip_clone(cloned)
+    }
+}
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl<I> Sealed for I where I: Iterator {}
 }
 
 impl<I> ZipClone for I
@@ -71,119 +406,1271 @@ where
     /// ```
     fn zip_clone<C>(self, cloned: C) -> ZipCloneIter<Self, C>
     where
-        C: Clone,
+        C: Clone,
+    {
+        zip_clone(self, cloned)
+    }
+
+    /// Zip an iterator to a copy-on-write value.
+    ///
+    /// A borrowed value is paired with every item without cloning. An owned
+    /// value is cloned one fewer times than the number of items.
+    ///
+    /// Example:
+    /// ```rust
+    /// use std::borrow::Cow;
+    /// use zip_clone::ZipClone;
+    ///
+    /// let iter = 0..10;
+    /// for (i, s) in iter.zip_cow(Cow::Borrowed("Hello")) {
+    ///     assert_eq!(s, "Hello");
+    /// }
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn zip_cow<'a, B>(self, cow: alloc::borrow::Cow<'a, B>) -> ZipCowIter<'a, Self, B>
+    where
+        B: alloc::borrow::ToOwned + ?Sized,
+    {
+        zip_cow(self, cow)
+    }
+
+    /// Map each item, together with a copy of a value, storing a single result
+    /// without allocating.
+    ///
+    /// A single item receives the original value, with no clones and no
+    /// allocation. More items are collected into a `Vec`, using one fewer
+    /// clones than items.
+    ///
+    /// Example:
+    /// ```rust
+    /// use zip_clone::{OneOrMany, ZipClone};
+    ///
+    /// let results = ["a"].iter().map_one_or_many(String::from("Hello"), |item, s| s + item);
+    /// assert_eq!(results, OneOrMany::One(String::from("Helloa")));
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn map_one_or_many<C, R, F>(self, value: C, f: F) -> OneOrMany<R>
+    where
+        C: Clone,
+        F: FnMut(Self::Item, C) -> R,
+    {
+        map_one_or_many(self, value, f)
+    }
+
+    /// Zip an iterator to a value shared through a reference-counted pointer.
+    ///
+    /// The value is never cloned. Each item is paired with a clone of the
+    /// pointer.
+    ///
+    /// Example:
+    /// ```rust
+    /// use std::sync::Arc;
+    /// use zip_clone::ZipClone;
+    ///
+    /// let s = String::from("Hello");
+    /// let iter = 0..10;
+    /// for (i, s) in iter.zip_shared::<Arc<_>>(s) {
+    ///     assert_eq!(*s, String::from("Hello"));
+    /// }
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn zip_shared<P>(self, value: P::Target) -> ZipCloneIter<Self, P>
+    where
+        P: SharedPointer,
+    {
+        zip_shared(self, value)
+    }
+}
+
+/// An iterator that pairs items with clones of a value.
+///
+/// This struct is created by [`zip_clone`] and [`ZipClone::zip_clone`]. The
+/// strategy `D` that creates the copies defaults to [`ByClone`]. Other
+/// strategies are used with [`zip_duplicate`].
+///
+/// [`Iterator::find`] lends the value to each rejected item, so only the found
+/// item needs a clone. [`Iterator::find_map`] cannot do the same, because its
+/// closure takes ownership of every pair it is given, so use `find` followed
+/// by `map` when the clones are expensive.
+pub struct ZipCloneIter<I, C, D = ByClone>
+where
+    I: Iterator,
+{
+    iter: Lookahead<I>,
+    cloned: Held<C>,
+    strategy: D,
+}
+
+// The value to be returned with the next item.
+struct Held<C> {
+    value: Option<C>,
+    // Whether the value is the one passed in, rather than a clone.
+    original: bool,
+    #[cfg(all(debug_assertions, feature = "warn-wasted-clones"))]
+    clones: usize,
+    #[cfg(feature = "trace")]
+    trace: alloc::vec::Vec<Operation>,
+}
+
+// Record an operation in the trace of a `Held` value, with the `trace` feature.
+macro_rules! record {
+    ($held:expr, $($op:tt)*) => {
+        #[cfg(feature = "trace")]
+        $held.trace.push($crate::Operation::$($op)*);
+    };
+}
+
+impl<C> Held<C> {
+    fn new(value: C) -> Self {
+        Held {
+            value: Some(value),
+            original: true,
+            #[cfg(all(debug_assertions, feature = "warn-wasted-clones"))]
+            clones: 0,
+            #[cfg(feature = "trace")]
+            trace: alloc::vec::Vec::new(),
+        }
+    }
+
+    fn take(&mut self) -> Option<C> {
+        self.value.take()
+    }
+
+    #[cfg(not(feature = "predictable"))]
+    fn put_back(&mut self, value: C) {
+        self.value = Some(value);
+    }
+
+    // Return a copy of the value for an item that is not the last, keeping
+    // the rest for the following items.
+    #[inline]
+    fn split_off<D>(&mut self, strategy: &mut D) -> Option<C>
+    where
+        D: Duplicate<C>,
+    {
+        let copy = strategy.duplicate(self.value.as_mut()?);
+        self.cloned();
+        Some(copy)
+    }
+
+    // Note that a copy was made for an item that is not the last. The folds
+    // call this directly, since they hold the value outside `Held` while
+    // folding.
+    #[inline]
+    fn cloned(&mut self) {
+        self.original = false;
+        record!(self, Cloned);
+        #[cfg(feature = "defmt")]
+        ::defmt::trace!("zip_clone: cloned value for the next item");
+        #[cfg(all(debug_assertions, feature = "warn-wasted-clones"))]
+        {
+            self.clones += 1;
+        }
+    }
+
+    // Note that a zero-sized value was cloned for an item.
+    #[inline]
+    fn cloned_zero_sized(&mut self) {
+        record!(self, Cloned);
+        #[cfg(feature = "defmt")]
+        ::defmt::trace!("zip_clone: cloned zero-sized value");
+    }
+
+    // Note that the value is moved into the last item.
+    #[inline]
+    fn moved(&mut self) {
+        record!(
+            self,
+            Moved {
+                original: self.original
+            }
+        );
+        #[cfg(feature = "defmt")]
+        ::defmt::trace!("zip_clone: moved value into the last item");
+    }
+}
+
+// With the `warn-wasted-clones` feature, debug builds warn when the iterator is
+// dropped holding a clone that was never used, since that clone could have been
+// avoided, e.g. by applying `take` before `zip_clone`. With the `defmt` feature,
+// dropping any unused value is logged.
+#[cfg(any(
+    all(debug_assertions, feature = "warn-wasted-clones"),
+    feature = "defmt"
+))]
+impl<C> Drop for Held<C> {
+    fn drop(&mut self) {
+        #[cfg(all(debug_assertions, feature = "warn-wasted-clones"))]
+        if self.value.is_some() && self.clones > 0 {
+            eprintln!(
+                "zip_clone: dropped holding an unused clone after making {} clones",
+                self.clones
+            );
+        }
+        #[cfg(feature = "defmt")]
+        if self.value.is_some() {
+            ::defmt::trace!(
+                "zip_clone: dropped an unused value (original: {=bool})",
+                self.original
+            );
+        }
+    }
+}
+
+impl<I, C> ZipCloneIter<I, C>
+where
+    I: Iterator,
+{
+    /// Zip an iterator that is already `Peekable` to a repeately cloned
+    /// object.
+    ///
+    /// This is [`zip_clone`], but uses the caller's `Peekable` for the
+    /// lookahead instead of wrapping it in another one. An item already peeked
+    /// is returned first.
+    ///
+    /// Example:
+    /// ```rust
+    /// use zip_clone::ZipCloneIter;
+    ///
+    /// let mut tokens = vec!["let", "x"].into_iter().peekable();
+    /// assert_eq!(tokens.peek(), Some(&"let"));
+    /// let mut iter = ZipCloneIter::from_peekable(tokens, String::from("Hello"));
+    /// assert_eq!(iter.peek(), Some(&"let"));
+    /// assert_eq!(iter.next(), Some(("let", String::from("Hello"))));
+    /// ```
+    pub fn from_peekable(iter: core::iter::Peekable<I>, cloned: C) -> Self
+    where
+        C: Clone,
+    {
+        ZipCloneIter {
+            iter: Lookahead::new(iter),
+            cloned: Held::new(cloned),
+            strategy: ByClone,
+        }
+    }
+}
+
+impl<I, C, D> ZipCloneIter<I, C, D>
+where
+    I: Iterator,
+{
+    /// Return a reference to the next item, without advancing the iterator.
+    ///
+    /// Returns `None` if the next call to `next` would return `None`. No clone
+    /// is made.
+    pub fn peek(&mut self) -> Option<&I::Item> {
+        self.cloned.value.as_ref()?;
+        self.iter.peek()
+    }
+
+    /// Install a new value, so that iteration can continue with any items that
+    /// the underlying iterator returns after it previously returned `None`.
+    ///
+    /// This is useful with iterators that are not fused, or that are refilled
+    /// after being exhausted. An end of iteration already seen by the
+    /// lookahead is discarded, so the next call to `next` asks the underlying
+    /// iterator for another item.
+    ///
+    /// Returns the value previously held, if any.
+    ///
+    /// Although `ZipCloneIter` implements `FusedIterator`, it may return items
+    /// after returning `None` if it is rearmed.
+    ///
+    /// Example:
+    /// ```rust
+    /// use std::sync::mpsc::channel;
+    /// use zip_clone::ZipClone;
+    ///
+    /// let (sender, receiver) = channel();
+    /// sender.send(1).unwrap();
+    /// let mut iter = receiver.try_iter().zip_clone(String::from("Hello"));
+    /// assert_eq!(iter.next(), Some((1, String::from("Hello"))));
+    /// assert_eq!(iter.next(), None);
+    /// sender.send(2).unwrap();
+    /// iter.rearm(String::from("World"));
+    /// assert_eq!(iter.next(), Some((2, String::from("World"))));
+    /// ```
+    pub fn rearm(&mut self, value: C) -> Option<C> {
+        if self.iter.peek().is_none() {
+            self.iter.next();
+        }
+        let previous = self.cloned.take();
+        #[cfg(feature = "trace")]
+        let trace = core::mem::take(&mut self.cloned.trace);
+        self.cloned = Held::new(value);
+        #[cfg(feature = "trace")]
+        {
+            self.cloned.trace = trace;
+        }
+        record!(self.cloned, Rearm);
+        previous
+    }
+
+    /// Return bounds on the number of clones that consuming the rest of the
+    /// iterator would make.
+    ///
+    /// The bounds are derived from the underlying iterator's `size_hint`, so
+    /// they are exact for an `ExactSizeIterator`. The upper bound is `None` if
+    /// the number of items is unbounded.
+    ///
+    /// Example:
+    /// ```rust
+    /// use zip_clone::ZipClone;
+    ///
+    /// let iter = (0..10).zip_clone(String::from("Hello"));
+    /// assert_eq!(iter.predicted_clones(), (9, Some(9)));
+    /// let iter = (0..10).filter(|i| i % 2 == 0).zip_clone(String::from("Hello"));
+    /// assert_eq!(iter.predicted_clones(), (0, Some(9)));
+    /// ```
+    pub fn predicted_clones(&self) -> (usize, Option<usize>) {
+        if self.cloned.value.is_none() {
+            return (0, Some(0));
+        }
+        let (lower, upper) = self.iter.size_hint();
+        if core::mem::size_of::<C>() == 0 && !cfg!(feature = "predictable") {
+            // Zero-sized values are cloned for every item.
+            return (lower, upper);
+        }
+        (
+            lower.saturating_sub(1),
+            upper.map(|upper| upper.saturating_sub(1)),
+        )
+    }
+
+    /// Return `true` if the iterator will not return any more items.
+    ///
+    /// This may ask the underlying iterator for its next item, which is kept
+    /// to be returned by the following call to `next`.
+    ///
+    /// Example:
+    /// ```rust
+    /// use zip_clone::ZipClone;
+    ///
+    /// let mut iter = (0..2).zip_clone(String::from("Hello"));
+    /// iter.next();
+    /// assert!(!iter.is_exhausted());
+    /// iter.next();
+    /// assert!(iter.is_exhausted());
+    /// ```
+    pub fn is_exhausted(&mut self) -> bool {
+        self.cloned.value.is_none() || self.iter.peek().is_none()
+    }
+
+    /// Return `true` if the iterator still holds the original value, rather
+    /// than a clone of it.
+    ///
+    /// The original value is held until the first item is returned, or, for
+    /// zero-sized values, until the iterator is exhausted.
+    ///
+    /// Example:
+    /// ```rust
+    /// use zip_clone::ZipClone;
+    ///
+    /// let mut iter = (0..2).zip_clone(String::from("Hello"));
+    /// assert!(iter.holds_original());
+    /// iter.next();
+    /// assert!(!iter.holds_original());
+    /// ```
+    pub fn holds_original(&self) -> bool {
+        self.cloned.original && self.cloned.value.is_some()
+    }
+
+    /// Return the operations performed on the iterator so far, including the
+    /// clones and moves of the value.
+    ///
+    /// This is useful to find out why a pipeline makes more clones than
+    /// expected.
+    ///
+    /// Example:
+    /// ```rust
+    /// use zip_clone::{Operation, ZipClone};
+    ///
+    /// let mut iter = (0..2).zip_clone(String::from("Hello"));
+    /// iter.next();
+    /// iter.next();
+    /// assert_eq!(
+    ///     iter.trace(),
+    ///     [
+    ///         Operation::Next,
+    ///         Operation::Cloned,
+    ///         Operation::Next,
+    ///         Operation::Moved { original: false },
+    ///     ]
+    /// );
+    /// ```
+    #[cfg(feature = "trace")]
+    pub fn trace(&self) -> &[Operation] {
+        &self.cloned.trace
+    }
+
+    /// Return the value held for the next item, if any, dropping the rest of
+    /// the iterator.
+    ///
+    /// The value is the original if no items were returned, or a clone made
+    /// for an item that was never returned. If the last item was returned, the
+    /// original value was returned with it and this returns `None`.
+    ///
+    /// Example:
+    /// ```rust
+    /// use zip_clone::ZipClone;
+    ///
+    /// let mut iter = (0..10).zip_clone(String::from("Hello"));
+    /// iter.next();
+    /// assert_eq!(iter.into_leftover(), Some(String::from("Hello")));
+    /// ```
+    pub fn into_leftover(mut self) -> Option<C> {
+        self.cloned.take()
+    }
+
+    /// Move the value held for the next item into a new `ZipCloneIter` over
+    /// another iterator, dropping the rest of this iterator.
+    ///
+    /// Multi-phase pipelines can use this to pass a value from one phase to
+    /// the next without cloning it at the boundary. Returns `None` if the
+    /// value was already returned with the last item. See
+    /// [`into_leftover`](Self::into_leftover).
+    ///
+    /// Example:
+    /// ```rust
+    /// use zip_clone::ZipClone;
+    ///
+    /// let mut phase1 = (0..10).zip_clone(String::from("Hello"));
+    /// for (i, s) in phase1.by_ref().take(5) {
+    ///     assert_eq!(s, "Hello");
+    /// }
+    /// let phase2 = phase1.carry_over(20..30).unwrap();
+    /// assert_eq!(phase2.map(|(i, _)| i).sum::<u32>(), 245);
+    /// ```
+    pub fn carry_over<J>(mut self, next: J) -> Option<ZipCloneIter<J::IntoIter, C, D>>
+    where
+        J: IntoIterator,
+        D: Duplicate<C>,
+    {
+        let strategy = self.strategy;
+        self.cloned
+            .take()
+            .map(|value| zip_duplicate(next.into_iter(), value, strategy))
+    }
+}
+
+impl<I, C> ZipCloneIter<I, C>
+where
+    I: Iterator,
+{
+    /// Call a fallible function with each item and value, stopping at the first
+    /// error.
+    ///
+    /// On error, the error is returned in a
+    /// [`RecoverableError`](error::RecoverableError) together with the value
+    /// that would have been given to the next item, so it can be reused, for
+    /// example to retry. The value is `None` if the error was returned for the
+    /// last item.
+    ///
+    /// Example:
+    /// ```rust
+    /// use zip_clone::ZipClone;
+    ///
+    /// let result = (0..10).zip_clone(String::from("Hello")).try_for_each_clone(|i, s| {
+    ///     if i == 3 {
+    ///         Err(i)
+    ///     } else {
+    ///         Ok(())
+    ///     }
+    /// });
+    /// let (i, s) = result.unwrap_err().into_parts();
+    /// assert_eq!(i, 3);
+    /// assert_eq!(s, Some(String::from("Hello")));
+    /// ```
+    pub fn try_for_each_clone<E, F>(
+        mut self,
+        mut f: F,
+    ) -> Result<(), error::RecoverableError<E, Option<C>>>
+    where
+        C: Clone,
+        F: FnMut(I::Item, C) -> Result<(), E>,
+    {
+        while let Some((item, value)) = self.next() {
+            if let Err(e) = f(item, value) {
+                return Err(error::RecoverableError::new(e, self.into_leftover()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Write the items and values into an existing vector, reusing the
+    /// allocations of the values already in it.
+    ///
+    /// Each pair is written over the element at the same position, with the
+    /// value written using [`Clone::clone_from`], so a value such as a
+    /// `String` can reuse the heap allocation of the value it replaces. The
+    /// vector is extended with clones if it is too short, and truncated to
+    /// the number of items if it is too long. The last item receives the
+    /// original value.
+    ///
+    /// Example:
+    /// ```rust
+    /// use zip_clone::ZipClone;
+    ///
+    /// let mut dest = vec![(0, String::with_capacity(100)); 2];
+    /// (0..3).zip_clone(String::from("Hello")).collect_into_reuse(&mut dest);
+    /// assert_eq!(dest[1], (1, String::from("Hello")));
+    /// assert_eq!(dest[1].1.capacity(), 100);
+    /// assert_eq!(dest.len(), 3);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn collect_into_reuse(mut self, dest: &mut alloc::vec::Vec<(I::Item, C)>)
+    where
+        C: Clone,
+    {
+        let mut len = 0;
+        if let Some(value) = self.cloned.take() {
+            while let Some(item) = self.iter.next() {
+                if self.iter.peek().is_none() {
+                    match dest.get_mut(len) {
+                        Some(slot) => *slot = (item, value),
+                        None => dest.push((item, value)),
+                    }
+                    len += 1;
+                    break;
+                }
+                match dest.get_mut(len) {
+                    Some(slot) => {
+                        slot.0 = item;
+                        slot.1.clone_from(&value);
+                    }
+                    None => dest.push((item, value.clone())),
+                }
+                len += 1;
+            }
+        }
+        dest.truncate(len);
+    }
+
+    /// Group the items and values into a map of vectors, by a key computed
+    /// from each item.
+    ///
+    /// Pairs are added to their groups in iteration order. The last item
+    /// receives the original value, so it is in the group of the last key, and
+    /// `n` items use `n - 1` clones.
+    ///
+    /// Example:
+    /// ```rust
+    /// use zip_clone::ZipClone;
+    ///
+    /// let groups = (0..5)
+    ///     .zip_clone(String::from("Hello"))
+    ///     .into_group_map_clone(|i| i % 2);
+    /// assert_eq!(groups[&0].len(), 3);
+    /// assert_eq!(groups[&1][1], (3, String::from("Hello")));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn into_group_map_clone<K, F>(
+        mut self,
+        mut key_fn: F,
+    ) -> std::collections::HashMap<K, alloc::vec::Vec<(I::Item, C)>>
+    where
+        C: Clone,
+        K: core::hash::Hash + Eq,
+        F: FnMut(&I::Item) -> K,
+    {
+        let mut groups = std::collections::HashMap::new();
+        if let Some(value) = self.cloned.take() {
+            while let Some(item) = self.iter.next() {
+                let group = groups
+                    .entry(key_fn(&item))
+                    .or_insert_with(alloc::vec::Vec::new);
+                if self.iter.peek().is_none() {
+                    group.push((item, value));
+                    break;
+                }
+                group.push((item, value.clone()));
+            }
+        }
+        groups
+    }
+
+    /// Call a function with the leftover value when the iterator is exhausted
+    /// or dropped.
+    ///
+    /// The function receives the original value if it was never returned with
+    /// an item, a spare clone if iteration stopped early, or `None` if the
+    /// value was returned with the last item. This can be used to return an
+    /// unused value to a pool.
+    ///
+    /// Example:
+    /// ```rust
+    /// use zip_clone::ZipClone;
+    ///
+    /// let mut pool = Vec::new();
+    /// for (i, s) in (0..0).zip_clone(String::from("Hello")).on_finish(|s| pool.extend(s)) {
+    ///     unreachable!();
+    /// }
+    /// assert_eq!(pool, ["Hello"]);
+    /// ```
+    pub fn on_finish<F>(self, f: F) -> OnFinish<I, C, F>
+    where
+        F: FnOnce(Option<C>),
+    {
+        OnFinish::new(self, f)
+    }
+
+    /// Call a function with a reference to each item and value before they
+    /// are returned.
+    ///
+    /// This is [`inspect_pair`] applied to this iterator. Unlike
+    /// [`Iterator::inspect`], it keeps the clone-saving behavior of `count`,
+    /// `last`, `nth` and `find`.
+    ///
+    /// Example:
+    /// ```rust
+    /// use zip_clone::ZipClone;
+    ///
+    /// let s = String::from("Hello");
+    /// let last = (0..10)
+    ///     .zip_clone(s)
+    ///     .inspect_pair(|i, s| println!("{}: {}", i, s))
+    ///     .last();
+    /// assert_eq!(last, Some((9, String::from("Hello"))));
+    /// ```
+    pub fn inspect_pair<F>(self, f: F) -> InspectPair<Self, F>
+    where
+        C: Clone,
+        F: FnMut(&I::Item, &C),
+    {
+        inspect_pair(self, f)
+    }
+
+    /// Discard the items, returning only the values.
+    ///
+    /// As for the pairs, the first remaining item gives the original value,
+    /// and each following item gives a clone. This is useful when the iterator
+    /// only decides how many copies of the value are needed.
+    ///
+    /// Example:
+    /// ```rust
+    /// use zip_clone::ZipClone;
+    ///
+    /// let copies = (0..3)
+    ///     .zip_clone(String::from("Hello"))
+    ///     .into_values()
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(copies, ["Hello", "Hello", "Hello"]);
+    /// ```
+    pub fn into_values(self) -> IntoValues<I, C> {
+        IntoValues::new(self)
+    }
+}
+
+impl<I, C, D> ZipCloneIter<I, C, D>
+where
+    I: Iterator,
+{
+    /// Map the items, keeping the value and the clone-saving overrides.
+    ///
+    /// Unlike applying [`Iterator::map`] to the pairs, the result is still a
+    /// `ZipCloneIter`, so `count`, `last`, `nth` and `find` continue to avoid
+    /// unnecessary clones. The function is only applied to the items.
+    ///
+    /// Example:
+    /// ```rust
+    /// use zip_clone::ZipClone;
+    ///
+    /// let iter = (0..10).zip_clone(String::from("Hello")).map_items(|i| i * 2);
+    /// assert_eq!(iter.last(), Some((18, String::from("Hello"))));
+    /// ```
+    pub fn map_items<B, F>(
+        self,
+        f: F,
+    ) -> ZipCloneIter<core::iter::Map<core::iter::Peekable<I>, F>, C, D>
+    where
+        F: FnMut(I::Item) -> B,
+    {
+        ZipCloneIter {
+            iter: self.iter.map(f),
+            cloned: self.cloned,
+            strategy: self.strategy,
+        }
+    }
+
+    /// Keep only the items for which a predicate returns `true`, keeping the
+    /// value and the clone-saving overrides.
+    ///
+    /// Unlike applying [`Iterator::filter`] to the pairs, the lookahead skips
+    /// rejected items, so no clones are made for rejected items, and the last
+    /// accepted item receives the held value without a further clone. As with
+    /// `zip_clone`, the original value is paired with the first accepted item.
+    ///
+    /// Example:
+    /// ```rust
+    /// use zip_clone::ZipClone;
+    ///
+    /// let mut iter = (0..10)
+    ///     .zip_clone(String::from("Hello"))
+    ///     .filter_items(|i| i % 3 == 0);
+    /// assert_eq!(iter.next(), Some((0, String::from("Hello"))));
+    /// assert_eq!(iter.next_back(), Some((9, String::from("Hello"))));
+    /// assert!(!iter.holds_original());
+    /// ```
+    pub fn filter_items<P>(
+        self,
+        predicate: P,
+    ) -> ZipCloneIter<core::iter::Filter<core::iter::Peekable<I>, P>, C, D>
+    where
+        P: FnMut(&I::Item) -> bool,
+    {
+        ZipCloneIter {
+            iter: self.iter.filter(predicate),
+            cloned: self.cloned,
+            strategy: self.strategy,
+        }
+    }
+
+    /// Continue with the items of another iterator after the items of this
+    /// one, sharing the value.
+    ///
+    /// As with `zip_clone`, the original value is paired with the first
+    /// remaining item. The last item of `other`, or the last remaining item of
+    /// this iterator if `other` is empty, receives the held value without a
+    /// further clone. The result keeps an exact length if both iterators have
+    /// one. If the value has already been paired with the last item, the
+    /// result returns no items.
+    ///
+    /// Example:
+    /// ```rust
+    /// use zip_clone::ZipClone;
+    ///
+    /// let iter = (0..3).zip_clone(String::from("Hello")).chain_items(3..5);
+    /// assert_eq!(iter.len(), 5);
+    /// assert_eq!(iter.last(), Some((4, String::from("Hello"))));
+    /// ```
+    pub fn chain_items<J>(
+        self,
+        other: J,
+    ) -> ZipCloneIter<ChainItems<core::iter::Peekable<I>, J::IntoIter>, C, D>
+    where
+        J: IntoIterator<Item = I::Item>,
+    {
+        ZipCloneIter {
+            iter: self.iter.chain(other.into_iter()),
+            cloned: self.cloned,
+            strategy: self.strategy,
+        }
+    }
+}
+
+impl<I, C, D> ZipCloneIter<I, C, D>
+where
+    I: Iterator,
+    D: Duplicate<C>,
+{
+    // Pair the item returned by `next` with the held value, keeping a clone
+    // for the following item if there is one. Inlined so that the panic-free
+    // check in `tests/no_panic.rs` can see through it.
+    #[inline]
+    fn pair_with<F>(&mut self, next: F) -> Option<(I::Item, C)>
+    where
+        F: FnOnce(&mut Lookahead<I>) -> Option<I::Item>,
+    {
+        if core::mem::size_of::<C>() == 0 && !cfg!(feature = "predictable") {
+            // Cloning a zero-sized value costs nothing, so do not peek at the
+            // next item, and return a clone with every item. The lookahead
+            // slots are still there, but stay empty.
+            let cloned = self.cloned.value.as_mut()?;
+            return match next(&mut self.iter) {
+                Some(item) => {
+                    let cloned = self.strategy.duplicate(cloned);
+                    self.cloned.cloned_zero_sized();
+                    Some((item, cloned))
+                }
+                None => {
+                    self.cloned.take();
+                    None
+                }
+            };
+        }
+        // Once the value is gone, leave the underlying iterator untouched.
+        self.cloned.value.as_ref()?;
+        let item = match next(&mut self.iter) {
+            Some(item) => item,
+            None => {
+                self.cloned.take();
+                return None;
+            }
+        };
+        if self.iter.peek().is_some() {
+            return Some((item, self.cloned.split_off(&mut self.strategy)?));
+        }
+        self.cloned.moved();
+        Some((item, self.cloned.take()?))
+    }
+
+    // Select one of the remaining items, where `replace` returns `true` if the
+    // candidate should replace the current choice. Pairs are only needed for
+    // comparison, so the value and a single clone are swapped between the
+    // current choice and each candidate, rather than cloning for every item.
+    #[cfg(not(feature = "predictable"))]
+    fn select_by<F>(mut self, mut replace: F) -> Option<(I::Item, C)>
+    where
+        F: FnMut(&(I::Item, C), &(I::Item, C)) -> bool,
+    {
+        let cloned = self.cloned.take()?;
+        let mut chosen = (self.iter.next()?, cloned);
+        let mut spare = None;
+        for item in self.iter {
+            let value = match spare.take() {
+                Some(value) => value,
+                None => self.strategy.duplicate(&mut chosen.1),
+            };
+            let candidate = (item, value);
+            if replace(&chosen, &candidate) {
+                spare = Some(core::mem::replace(&mut chosen, candidate).1);
+            } else {
+                spare = Some(candidate.1);
+            }
+        }
+        Some(chosen)
+    }
+}
+
+impl<I, C, D> Iterator for ZipCloneIter<I, C, D>
+where
+    I: Iterator,
+    D: Duplicate<C>,
+{
+    type Item = (I::Item, C);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        record!(self.cloned, Next);
+        self.pair_with(|iter| iter.next())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    #[cfg(not(feature = "predictable"))]
+    fn count(self) -> usize
+    where
+        Self: Sized,
+    {
+        self.iter.count()
+    }
+
+    #[cfg(not(feature = "predictable"))]
+    fn last(mut self) -> Option<Self::Item>
+    where
+        Self: Sized,
+    {
+        match (self.cloned.take(), self.iter.last()) {
+            (Some(cloned), Some(item)) => {
+                // iterator is fully consumed so no need to replace clone
+                Some((item, cloned))
+            }
+            _ => None,
+        }
+    }
+
+    // Inlined, as for `pair_with`.
+    #[cfg(not(feature = "predictable"))]
+    #[inline]
+    fn find<P>(&mut self, mut predicate: P) -> Option<Self::Item>
+    where
+        Self: Sized,
+        P: FnMut(&Self::Item) -> bool,
+    {
+        // Lend the value to each rejected item, and only clone it if the found
+        // item is followed by more items.
+        record!(self.cloned, Find);
+        let mut cloned = self.cloned.take()?;
+        while let Some(item) = self.iter.next() {
+            let pair = (item, cloned);
+            if predicate(&pair) {
+                if self.iter.peek().is_some() {
+                    let (item, value) = pair;
+                    self.cloned.put_back(value);
+                    return Some((item, self.cloned.split_off(&mut self.strategy)?));
+                }
+                self.cloned.moved();
+                return Some(pair);
+            }
+            cloned = pair.1;
+        }
+        None
+    }
+
+    #[cfg(not(feature = "predictable"))]
+    fn max_by<F>(self, mut compare: F) -> Option<Self::Item>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item, &Self::Item) -> core::cmp::Ordering,
+    {
+        // Equal items are replaced, so the last maximum is returned.
+        self.select_by(|chosen, candidate| {
+            compare(chosen, candidate) != core::cmp::Ordering::Greater
+        })
+    }
+
+    #[cfg(not(feature = "predictable"))]
+    fn min_by<F>(self, mut compare: F) -> Option<Self::Item>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item, &Self::Item) -> core::cmp::Ordering,
+    {
+        // Equal items are kept, so the first minimum is returned.
+        self.select_by(|chosen, candidate| {
+            compare(chosen, candidate) == core::cmp::Ordering::Greater
+        })
+    }
+
+    #[cfg(not(feature = "predictable"))]
+    fn max_by_key<B, F>(self, mut f: F) -> Option<Self::Item>
+    where
+        Self: Sized,
+        B: Ord,
+        F: FnMut(&Self::Item) -> B,
+    {
+        // Keep the key of the chosen item, so that `f` is called once per item.
+        let mut chosen_key = None;
+        self.select_by(|chosen, candidate| {
+            let key = chosen_key.take().unwrap_or_else(|| f(chosen));
+            let candidate_key = f(candidate);
+            let replace = candidate_key >= key;
+            chosen_key = Some(if replace { candidate_key } else { key });
+            replace
+        })
+    }
+
+    #[cfg(not(feature = "predictable"))]
+    fn min_by_key<B, F>(self, mut f: F) -> Option<Self::Item>
+    where
+        Self: Sized,
+        B: Ord,
+        F: FnMut(&Self::Item) -> B,
+    {
+        let mut chosen_key = None;
+        self.select_by(|chosen, candidate| {
+            let key = chosen_key.take().unwrap_or_else(|| f(chosen));
+            let candidate_key = f(candidate);
+            let replace = candidate_key < key;
+            chosen_key = Some(if replace { candidate_key } else { key });
+            replace
+        })
+    }
+
+    #[cfg(all(feature = "nightly", not(feature = "predictable")))]
+    fn try_find<R>(
+        &mut self,
+        mut f: impl FnMut(&Self::Item) -> R,
+    ) -> <R::Residual as core::ops::Residual<Option<Self::Item>>>::TryType
+    where
+        Self: Sized,
+        R: core::ops::Try<Output = bool>,
+        R::Residual: core::ops::Residual<Option<Self::Item>>,
     {
-        zip_clone(self, cloned)
+        use core::ops::{ControlFlow, FromResidual, Try};
+
+        // As for `find`, but an error leaves the value in place so that
+        // iteration can continue after the failed item.
+        record!(self.cloned, TryFind);
+        let mut cloned = match self.cloned.take() {
+            Some(cloned) => cloned,
+            None => return Try::from_output(None),
+        };
+        while let Some(item) = self.iter.next() {
+            let pair = (item, cloned);
+            match f(&pair).branch() {
+                ControlFlow::Continue(true) => {
+                    if self.iter.peek().is_some() {
+                        let (item, value) = pair;
+                        self.cloned.put_back(value);
+                        let pair = self
+                            .cloned
+                            .split_off(&mut self.strategy)
+                            .map(|value| (item, value));
+                        return Try::from_output(pair);
+                    }
+                    self.cloned.moved();
+                    return Try::from_output(Some(pair));
+                }
+                ControlFlow::Continue(false) => cloned = pair.1,
+                ControlFlow::Break(residual) => {
+                    if self.iter.peek().is_some() {
+                        self.cloned.put_back(pair.1);
+                    }
+                    return FromResidual::from_residual(residual);
+                }
+            }
+        }
+        Try::from_output(None)
     }
-}
 
-pub struct ZipCloneIter<I, C>
-where
-    I: Iterator,
-{
-    iter: std::iter::Peekable<I>,
-    cloned: Option<C>,
-}
+    #[cfg(not(feature = "predictable"))]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        record!(self.cloned, Nth(n));
+        self.pair_with(|iter| iter.nth(n))
+    }
 
-impl<I, C> Iterator for ZipCloneIter<I, C>
-where
-    I: Iterator,
-    C: Clone,
-{
-    type Item = (I::Item, C);
+    #[cfg(all(feature = "nightly", not(feature = "predictable")))]
+    fn try_fold<B, F, R>(&mut self, init: B, mut f: F) -> R
+    where
+        Self: Sized,
+        F: FnMut(B, Self::Item) -> R,
+        R: core::ops::Try<Output = B>,
+    {
+        use core::ops::{ControlFlow, FromResidual, Try};
 
-    fn next(&mut self) -> Option<Self::Item> {
-        match (self.cloned.take(), self.iter.next()) {
-            (Some(cloned), Some(item)) => {
-                if self.iter.peek().is_some() {
-                    self.cloned = Some(cloned.clone());
+        let mut cloned = match self.cloned.take() {
+            Some(cloned) => cloned,
+            None => return Try::from_output(init),
+        };
+        let held = &mut self.cloned;
+        let strategy = &mut self.strategy;
+        if core::mem::size_of::<C>() == 0 {
+            // As for `pair_with`, zero-sized values do not peek at the next
+            // item and clone for every item.
+            return match self
+                .iter
+                .try_fold(init, |acc, item| {
+                    let copy = strategy.duplicate(&mut cloned);
+                    held.cloned_zero_sized();
+                    f(acc, (item, copy))
+                })
+                .branch()
+            {
+                ControlFlow::Continue(acc) => Try::from_output(acc),
+                ControlFlow::Break(residual) => {
+                    self.cloned.put_back(cloned);
+                    FromResidual::from_residual(residual)
                 }
-                Some((item, cloned))
+            };
+        }
+        // Drive the inner `try_fold` one item behind, as for `fold`. If `f`
+        // stops early, the following item has already been taken, so it is
+        // pushed back along with the value.
+        let folded = self.iter.try_fold((init, None), |(acc, prev), item| {
+            let acc = match prev {
+                Some(prev) => {
+                    let copy = strategy.duplicate(&mut cloned);
+                    held.cloned();
+                    match f(acc, (prev, copy)).branch() {
+                        ControlFlow::Continue(acc) => acc,
+                        ControlFlow::Break(residual) => {
+                            return ControlFlow::Break((residual, item))
+                        }
+                    }
+                }
+                None => acc,
+            };
+            ControlFlow::Continue((acc, Some(item)))
+        });
+        match folded {
+            ControlFlow::Continue((acc, Some(item))) => {
+                held.moved();
+                f(acc, (item, cloned))
+            }
+            ControlFlow::Continue((acc, None)) => Try::from_output(acc),
+            ControlFlow::Break((residual, item)) => {
+                self.iter.push_front(item);
+                self.cloned.put_back(cloned);
+                FromResidual::from_residual(residual)
             }
-            _ => None,
         }
     }
 
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        self.iter.size_hint()
+    #[cfg(all(feature = "nightly", not(feature = "predictable")))]
+    fn try_for_each<F, R>(&mut self, mut f: F) -> R
+    where
+        Self: Sized,
+        F: FnMut(Self::Item) -> R,
+        R: core::ops::Try<Output = ()>,
+    {
+        self.try_fold((), |(), item| f(item))
     }
 
-    fn count(self) -> usize
+    fn for_each<F>(self, mut f: F)
     where
         Self: Sized,
+        F: FnMut(Self::Item),
     {
-        self.iter.count()
+        self.fold((), |(), item| f(item));
     }
 
-    fn last(mut self) -> Option<Self::Item>
+    fn fold<B, F>(mut self, init: B, mut f: F) -> B
     where
         Self: Sized,
+        F: FnMut(B, Self::Item) -> B,
     {
-        match (self.cloned.take(), self.iter.last()) {
-            (Some(cloned), Some(item)) => {
-                // iterator is fully consumed so no need to replace clone
-                Some((item, cloned))
-            }
-            _ => None,
+        // Drive the inner fold one item behind, so that each item is paired
+        // with a clone once another item is seen to follow it, and the last
+        // item gets the held value.
+        let mut cloned = match self.cloned.take() {
+            Some(cloned) => cloned,
+            None => return init,
+        };
+        let held = &mut self.cloned;
+        let strategy = &mut self.strategy;
+        if core::mem::size_of::<C>() == 0 && !cfg!(feature = "predictable") {
+            // As for `pair_with`, zero-sized values do not run one item behind
+            // and clone for every item.
+            return self.iter.fold(init, |acc, item| {
+                let copy = strategy.duplicate(&mut cloned);
+                held.cloned_zero_sized();
+                f(acc, (item, copy))
+            });
         }
-    }
-
-    fn nth(&mut self, n: usize) -> Option<Self::Item> {
-        match (self.cloned.take(), self.iter.nth(n)) {
-            (Some(cloned), Some(item)) => {
-                if self.iter.peek().is_some() {
-                    self.cloned = Some(cloned.clone());
+        let (acc, last) = self.iter.fold((init, None), |(acc, prev), item| {
+            let acc = match prev {
+                Some(prev) => {
+                    let copy = strategy.duplicate(&mut cloned);
+                    held.cloned();
+                    f(acc, (prev, copy))
                 }
-                Some((item, cloned))
+                None => acc,
+            };
+            (acc, Some(item))
+        });
+        match last {
+            Some(item) => {
+                held.moved();
+                f(acc, (item, cloned))
             }
-            _ => None,
+            None => acc,
         }
     }
 }
 
-impl<I, C> DoubleEndedIterator for ZipCloneIter<I, C>
+impl<I, C, D> DoubleEndedIterator for ZipCloneIter<I, C, D>
 where
     I: DoubleEndedIterator,
-    C: Clone,
+    D: Duplicate<C>,
 {
     fn next_back(&mut self) -> Option<Self::Item> {
-        match (self.cloned.take(), self.iter.next_back()) {
-            (Some(cloned), Some(item)) => {
-                if self.iter.peek().is_some() {
-                    self.cloned = Some(cloned.clone());
+        record!(self.cloned, NextBack);
+        self.pair_with(|iter| iter.next_back())
+    }
+
+    #[cfg(not(feature = "predictable"))]
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        record!(self.cloned, NthBack(n));
+        self.pair_with(|iter| iter.nth_back(n))
+    }
+
+    #[cfg(all(feature = "nightly", not(feature = "predictable")))]
+    fn try_rfold<B, F, R>(&mut self, init: B, mut f: F) -> R
+    where
+        Self: Sized,
+        F: FnMut(B, Self::Item) -> R,
+        R: core::ops::Try<Output = B>,
+    {
+        use core::ops::{ControlFlow, FromResidual, Try};
+
+        // As for `try_fold`, running one item behind the inner `try_rfold`.
+        let mut cloned = match self.cloned.take() {
+            Some(cloned) => cloned,
+            None => return Try::from_output(init),
+        };
+        let held = &mut self.cloned;
+        let strategy = &mut self.strategy;
+        if core::mem::size_of::<C>() == 0 {
+            return match self
+                .iter
+                .try_rfold(init, |acc, item| {
+                    let copy = strategy.duplicate(&mut cloned);
+                    held.cloned_zero_sized();
+                    f(acc, (item, copy))
+                })
+                .branch()
+            {
+                ControlFlow::Continue(acc) => Try::from_output(acc),
+                ControlFlow::Break(residual) => {
+                    self.cloned.put_back(cloned);
+                    FromResidual::from_residual(residual)
                 }
-                Some((item, cloned))
+            };
+        }
+        let folded = self.iter.try_rfold((init, None), |(acc, prev), item| {
+            let acc = match prev {
+                Some(prev) => {
+                    let copy = strategy.duplicate(&mut cloned);
+                    held.cloned();
+                    match f(acc, (prev, copy)).branch() {
+                        ControlFlow::Continue(acc) => acc,
+                        ControlFlow::Break(residual) => {
+                            return ControlFlow::Break((residual, item))
+                        }
+                    }
+                }
+                None => acc,
+            };
+            ControlFlow::Continue((acc, Some(item)))
+        });
+        match folded {
+            ControlFlow::Continue((acc, Some(item))) => {
+                held.moved();
+                f(acc, (item, cloned))
+            }
+            ControlFlow::Continue((acc, None)) => Try::from_output(acc),
+            ControlFlow::Break((residual, item)) => {
+                self.iter.push_back(item);
+                self.cloned.put_back(cloned);
+                FromResidual::from_residual(residual)
             }
-            _ => None,
         }
     }
 
-    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
-        match (self.cloned.take(), self.iter.nth_back(n)) {
-            (Some(cloned), Some(item)) => {
-                if self.iter.peek().is_some() {
-                    self.cloned = Some(cloned.clone());
+    fn rfold<B, F>(mut self, init: B, mut f: F) -> B
+    where
+        Self: Sized,
+        F: FnMut(B, Self::Item) -> B,
+    {
+        // As for `fold`, running one item behind the inner `rfold`.
+        let mut cloned = match self.cloned.take() {
+            Some(cloned) => cloned,
+            None => return init,
+        };
+        let held = &mut self.cloned;
+        let strategy = &mut self.strategy;
+        if core::mem::size_of::<C>() == 0 && !cfg!(feature = "predictable") {
+            return self.iter.rfold(init, |acc, item| {
+                let copy = strategy.duplicate(&mut cloned);
+                held.cloned_zero_sized();
+                f(acc, (item, copy))
+            });
+        }
+        let (acc, last) = self.iter.rfold((init, None), |(acc, prev), item| {
+            let acc = match prev {
+                Some(prev) => {
+                    let copy = strategy.duplicate(&mut cloned);
+                    held.cloned();
+                    f(acc, (prev, copy))
                 }
-                Some((item, cloned))
+                None => acc,
+            };
+            (acc, Some(item))
+        });
+        match last {
+            Some(item) => {
+                held.moved();
+                f(acc, (item, cloned))
             }
-            _ => None,
+            None => acc,
         }
     }
 }
 
-impl<I, C> ExactSizeIterator for ZipCloneIter<I, C>
+impl<I, C, D> ExactSizeIterator for ZipCloneIter<I, C, D>
 where
     I: ExactSizeIterator,
-    C: Clone,
+    D: Duplicate<C>,
 {
 }
 
 // `ZipCloneIter` is fused because, once the clone is removed and not replaced,
 // it will always return `None` for subsequent calls.
-impl<I, C> std::iter::FusedIterator for ZipCloneIter<I, C>
+impl<I, C, D> core::iter::FusedIterator for ZipCloneIter<I, C, D>
 where
     I: Iterator,
-    C: Clone,
+    D: Duplicate<C>,
 {
 }
 
@@ -191,10 +1678,10 @@ where
 mod tests {
     use std::sync::atomic::{AtomicU32, Ordering};
 
-    use crate::zip_clone;
+    use crate::{zip_clone, ZipCloneIter};
 
-    struct Clonable<'a> {
-        count: &'a AtomicU32,
+    pub(crate) struct Clonable<'a> {
+        pub(crate) count: &'a AtomicU32,
     }
     impl<'a> Clone for Clonable<'a> {
         fn clone(&self) -> Self {
@@ -227,6 +1714,115 @@ mod tests {
     }
 
     #[test]
+    fn test_zip_fold() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let mut iter = zip_clone(1..6, cloned);
+        assert_eq!(iter.next().unwrap().0, 1);
+        let sum = iter.fold(0, |acc, (item, _)| acc * 10 + item);
+        assert_eq!(sum, 2345);
+        assert_eq!(count.load(Ordering::Relaxed), 4);
+        let cloned = Clonable { count: &count };
+        assert_eq!(zip_clone(0..0, cloned).fold(0, |acc, _| acc + 1), 0);
+        assert_eq!(count.load(Ordering::Relaxed), 4);
+    }
+
+    #[test]
+    fn test_zip_for_each() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let mut items = vec![0; 5];
+        zip_clone(items.iter_mut(), cloned)
+            .for_each(|(item, cloned)| *item = cloned.count.load(Ordering::Relaxed));
+        assert_eq!(items, [1, 2, 3, 4, 4]);
+        assert_eq!(count.load(Ordering::Relaxed), 4);
+    }
+
+    #[test]
+    fn test_zip_rfold() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let mut iter = zip_clone(1..6, cloned);
+        assert_eq!(iter.next().unwrap().0, 1);
+        let digits = iter.rfold(0, |acc, (item, _)| acc * 10 + item);
+        assert_eq!(digits, 5432);
+        assert_eq!(count.load(Ordering::Relaxed), 4);
+        let cloned = Clonable { count: &count };
+        assert_eq!(zip_clone(0..0, cloned).rfold(0, |acc, _| acc + 1), 0);
+        assert_eq!(count.load(Ordering::Relaxed), 4);
+    }
+
+    #[cfg(all(feature = "nightly", not(feature = "predictable")))]
+    #[test]
+    fn test_zip_try_fold() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let mut iter = zip_clone(1..6, cloned);
+        let stopped = iter.try_fold(
+            0,
+            |acc, (item, _)| {
+                if item == 3 {
+                    Err(acc)
+                } else {
+                    Ok(acc + item)
+                }
+            },
+        );
+        assert_eq!(stopped, Err(3));
+        assert_eq!(count.load(Ordering::Relaxed), 3);
+        assert!(!iter.holds_original());
+        let sum = iter.try_fold(0, |acc, (item, _)| Some(acc + item));
+        assert_eq!(sum, Some(9));
+        assert_eq!(count.load(Ordering::Relaxed), 4);
+        assert!(iter.next().is_none());
+    }
+
+    #[cfg(all(feature = "nightly", not(feature = "predictable")))]
+    #[test]
+    fn test_zip_try_for_each() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let mut iter = zip_clone(1..6, cloned);
+        let result = iter.try_for_each(|(item, _)| if item < 2 { Ok(()) } else { Err(item) });
+        assert_eq!(result, Err(2));
+        assert_eq!(count.load(Ordering::Relaxed), 2);
+        assert_eq!(iter.try_for_each(|_| Ok::<_, u32>(())), Ok(()));
+        assert_eq!(count.load(Ordering::Relaxed), 4);
+        assert!(iter.into_leftover().is_none());
+    }
+
+    #[cfg(all(feature = "nightly", not(feature = "predictable")))]
+    #[test]
+    fn test_zip_try_rfold() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let mut iter = zip_clone(1..6, cloned);
+        assert_eq!(iter.rposition(|(item, _)| item == 4), Some(3));
+        assert_eq!(count.load(Ordering::Relaxed), 2);
+        assert!(!iter.holds_original());
+        let rest = iter.try_rfold(0, |acc, (item, _)| Some(acc * 10 + item));
+        assert_eq!(rest, Some(321));
+        assert_eq!(count.load(Ordering::Relaxed), 4);
+        assert!(iter.next_back().is_none());
+    }
+
+    #[cfg(all(feature = "nightly", not(feature = "predictable")))]
+    #[test]
+    fn test_zip_try_fold_pushed_back() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let mut iter = zip_clone(1..6, cloned);
+        assert_eq!(iter.position(|(item, _)| item == 2), Some(1));
+        assert_eq!(iter.rposition(|(item, _)| item == 4), Some(1));
+        assert_eq!(iter.len(), 1);
+        let iter = iter.map_items(|item| item * 10).chain_items(6..8);
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.map(|(item, _)| item).collect::<Vec<_>>(), [30, 6, 7]);
+        assert_eq!(count.load(Ordering::Relaxed), 6);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "predictable", ignore)]
     fn test_zip_count() {
         let iter = 1..6;
         let count = AtomicU32::new(0);
@@ -236,6 +1832,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(feature = "predictable", ignore)]
     fn test_zip_last() {
         let iter = 1..6;
         let count = AtomicU32::new(0);
@@ -243,4 +1840,351 @@ mod tests {
         assert_eq!(zip_clone(iter, cloned).last().unwrap().0, 5);
         assert_eq!(count.load(Ordering::Relaxed), 0);
     }
+
+    #[test]
+    #[cfg_attr(feature = "predictable", ignore)]
+    fn test_zip_max_min_by() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let iter = zip_clone(vec![3, 1, 4, 1, 5, 9, 2, 6, 9].into_iter(), cloned);
+        let max = iter.max_by(|(a, _), (b, _)| a.cmp(b));
+        assert_eq!(max.unwrap().0, 9);
+        assert_eq!(count.load(Ordering::Relaxed), 1);
+        let cloned = Clonable { count: &count };
+        let iter = zip_clone(vec![3, 1, 4, 1, 5].into_iter().enumerate(), cloned);
+        assert_eq!(iter.min_by(|a, b| a.0 .1.cmp(&b.0 .1)).unwrap().0, (1, 1));
+        assert_eq!(count.load(Ordering::Relaxed), 2);
+        let cloned = Clonable { count: &count };
+        assert_eq!(
+            zip_clone(0..1, cloned)
+                .max_by(|_, _| core::cmp::Ordering::Less)
+                .unwrap()
+                .0,
+            0
+        );
+        assert_eq!(count.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "predictable", ignore)]
+    fn test_zip_max_min_by_key() {
+        let count = AtomicU32::new(0);
+        let calls = core::cell::Cell::new(0);
+        let key = |((_, k), _): &((usize, u32), Clonable)| {
+            calls.set(calls.get() + 1);
+            *k
+        };
+        let items = || vec![2, 7, 1, 7, 1].into_iter().enumerate();
+        let cloned = Clonable { count: &count };
+        assert_eq!(
+            zip_clone(items(), cloned).max_by_key(key).unwrap().0,
+            (3, 7)
+        );
+        let cloned = Clonable { count: &count };
+        assert_eq!(
+            zip_clone(items(), cloned).min_by_key(key).unwrap().0,
+            (2, 1)
+        );
+        assert_eq!(calls.get(), 10);
+        assert_eq!(count.load(Ordering::Relaxed), 2);
+        let cloned = Clonable { count: &count };
+        assert!(zip_clone(0..0, cloned).min_by_key(|(i, _)| *i).is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "predictable")]
+    fn test_zip_predictable() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        assert!(zip_clone(1..6, cloned).last().is_some());
+        assert_eq!(count.load(Ordering::Relaxed), 4);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "predictable", ignore)]
+    fn test_zip_find() {
+        let iter = 1..6;
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let mut iter = zip_clone(iter, cloned);
+        assert_eq!(iter.find(|(item, _)| *item == 3).unwrap().0, 3);
+        assert_eq!(count.load(Ordering::Relaxed), 1);
+        assert!(iter.find(|(item, _)| *item == 3).is_none());
+        assert_eq!(count.load(Ordering::Relaxed), 1);
+    }
+
+    #[cfg(all(feature = "nightly", not(feature = "predictable")))]
+    #[test]
+    fn test_zip_try_find() {
+        let iter = 1..6;
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let mut iter = zip_clone(iter, cloned);
+        let found = iter.try_find(|(item, _)| if *item == 2 { Err(()) } else { Ok(false) });
+        assert!(found.is_err());
+        assert_eq!(count.load(Ordering::Relaxed), 0);
+        let found = iter.try_find(|(item, _)| Ok::<_, ()>(*item == 4));
+        assert_eq!(found.unwrap().unwrap().0, 4);
+        assert_eq!(count.load(Ordering::Relaxed), 1);
+        assert_eq!(iter.map(|(item, _)| item).collect::<Vec<_>>(), [5]);
+        assert_eq!(count.load(Ordering::Relaxed), 1);
+    }
+
+    #[cfg(feature = "std")]
+    type MapWhileFn = fn(u32, &String) -> Option<u32>;
+    #[cfg(feature = "std")]
+    type InspectFn = fn(&u32, &String);
+
+    #[test]
+    #[cfg_attr(feature = "predictable", ignore)]
+    fn test_zip_zero_sized() {
+        static COUNT: AtomicU32 = AtomicU32::new(0);
+        struct Marker;
+        impl Clone for Marker {
+            fn clone(&self) -> Self {
+                COUNT.fetch_add(1, Ordering::Relaxed);
+                Marker
+            }
+        }
+        let mut iter = zip_clone(1..6, Marker);
+        assert_eq!(iter.by_ref().map(|(item, _)| item).sum::<u32>(), 15);
+        assert!(iter.next().is_none());
+        assert!(iter.into_leftover().is_none());
+        // Every item receives a clone, since zero-sized clones are free.
+        assert_eq!(COUNT.load(Ordering::Relaxed), 5);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "predictable", ignore)]
+    fn test_zip_zero_sized_fold() {
+        static COUNT: AtomicU32 = AtomicU32::new(0);
+        struct Marker;
+        impl Clone for Marker {
+            fn clone(&self) -> Self {
+                COUNT.fetch_add(1, Ordering::Relaxed);
+                Marker
+            }
+        }
+        let sum = zip_clone(1..6, Marker).fold(0, |acc, (item, _)| acc + item);
+        assert_eq!(sum, 15);
+        assert_eq!(COUNT.load(Ordering::Relaxed), 5);
+        let mut items = Vec::new();
+        zip_clone(1..4, Marker).rfold((), |(), (item, _)| items.push(item));
+        assert_eq!(items, [3, 2, 1]);
+        assert_eq!(COUNT.load(Ordering::Relaxed), 8);
+    }
+
+    #[test]
+    fn test_predicted_clones() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let mut iter = zip_clone(1..6, cloned);
+        assert_eq!(iter.predicted_clones(), (4, Some(4)));
+        iter.next();
+        assert_eq!(iter.predicted_clones(), (3, Some(3)));
+        assert_eq!(iter.by_ref().map(|_| ()).count(), 4);
+        assert_eq!(count.load(Ordering::Relaxed), 4);
+        assert_eq!(iter.predicted_clones(), (0, Some(0)));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_collect_into_reuse() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let mut dest = Vec::new();
+        zip_clone(1..6, cloned.clone()).collect_into_reuse(&mut dest);
+        assert_eq!(dest.len(), 5);
+        assert_eq!(count.load(Ordering::Relaxed), 5);
+        zip_clone(1..3, cloned).collect_into_reuse(&mut dest);
+        assert_eq!(dest.len(), 2);
+        assert_eq!(count.load(Ordering::Relaxed), 6);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_into_group_map_clone() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let groups = zip_clone(0..7, cloned).into_group_map_clone(|i| i % 3);
+        assert_eq!(groups.len(), 3);
+        assert_eq!(groups[&0].len(), 3);
+        assert_eq!(count.load(Ordering::Relaxed), 6);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_repeat_clones_original_last() {
+        let value = String::from("Hello");
+        let ptr = value.as_ptr();
+        let copies = crate::repeat_clones(value, 3).collect::<Vec<_>>();
+        assert_eq!(copies, ["Hello", "Hello", "Hello"]);
+        assert_ne!(copies[0].as_ptr(), ptr);
+        assert_ne!(copies[1].as_ptr(), ptr);
+        assert_eq!(copies[2].as_ptr(), ptr);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_into_group_map_clone_original_last() {
+        let value = String::from("Hello");
+        let ptr = value.as_ptr();
+        let groups = zip_clone(0..5, value).into_group_map_clone(|i| i % 2);
+        assert_eq!(groups[&0][2].0, 4);
+        assert_eq!(groups[&0][2].1.as_ptr(), ptr);
+        assert!(groups[&1].iter().all(|(_, s)| s.as_ptr() != ptr));
+    }
+
+    #[test]
+    fn test_from_peekable() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let mut source = (1..4).peekable();
+        assert_eq!(source.peek(), Some(&1));
+        let mut iter = ZipCloneIter::from_peekable(source, cloned);
+        assert_eq!(iter.peek(), Some(&1));
+        assert_eq!(iter.by_ref().map(|(i, _)| i).sum::<i32>(), 6);
+        assert_eq!(iter.peek(), None);
+        assert_eq!(count.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "predictable", ignore)]
+    fn test_map_items() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let mut iter = zip_clone(1..6, cloned).map_items(|i| i * 10);
+        assert_eq!(iter.next().map(|(i, _)| i), Some(10));
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.find(|(i, _)| *i == 30).map(|(i, _)| i), Some(30));
+        assert_eq!(iter.last().map(|(i, _)| i), Some(50));
+        assert_eq!(count.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn test_filter_items() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let iter = zip_clone(1..10, cloned).filter_items(|i| i % 4 == 0);
+        assert_eq!(iter.map(|(i, _)| i).collect::<Vec<_>>(), [4, 8]);
+        assert_eq!(count.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn test_exhausted_and_original() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let mut iter = zip_clone(1..3, cloned);
+        assert!(iter.holds_original());
+        assert!(!iter.is_exhausted());
+        iter.next();
+        assert!(!iter.holds_original());
+        assert!(!iter.is_exhausted());
+        iter.next();
+        assert!(!iter.holds_original());
+        assert!(iter.is_exhausted());
+        assert_eq!(count.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn test_try_for_each_clone() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let result = zip_clone(1..6, cloned).try_for_each_clone(|item, _| match item {
+            2 => Err(item),
+            _ => Ok(()),
+        });
+        let (e, leftover) = result.unwrap_err().into_parts();
+        assert_eq!(e, 2);
+        assert!(leftover.is_some());
+        assert_eq!(count.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn test_try_for_each_clone_last() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let result = zip_clone(1..6, cloned).try_for_each_clone(|item, _| match item {
+            5 => Err(item),
+            _ => Ok(()),
+        });
+        let (e, leftover) = result.unwrap_err().into_parts();
+        assert_eq!(e, 5);
+        assert!(leftover.is_none());
+        assert_eq!(count.load(Ordering::Relaxed), 4);
+    }
+
+    #[test]
+    fn test_carry_over() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let phase1 = zip_clone(0..0, cloned);
+        let phase2 = phase1.carry_over(1..4).unwrap();
+        assert_eq!(phase2.map(|_| ()).count(), 3);
+        assert_eq!(count.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn test_carry_over_after_last() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let mut phase1 = zip_clone(1..4, cloned);
+        assert_eq!(phase1.by_ref().map(|_| ()).count(), 3);
+        assert!(phase1.carry_over(1..4).is_none());
+        assert_eq!(count.load(Ordering::Relaxed), 2);
+    }
+
+    // All adapters are nameable, so they can be stored in struct fields.
+    #[cfg(feature = "std")]
+    #[allow(dead_code)]
+    struct Pipelines<'a> {
+        zip_clone: crate::ZipCloneIter<std::ops::Range<u32>, String>,
+        zip_clone_background: crate::ZipCloneBackground<std::ops::Range<u32>, String>,
+        zip_clone_batched: crate::ZipCloneBatched<std::ops::Range<u32>, String>,
+        zip_clone_original_by_key: crate::ZipCloneOriginalByKey<u32, String>,
+        with_position_clone: crate::WithPositionClone<std::ops::Range<u32>, String>,
+        map_while_clone: crate::MapWhileClone<std::ops::Range<u32>, String, u32, MapWhileFn>,
+        inspect_pair:
+            crate::InspectPair<crate::ZipCloneIter<std::ops::Range<u32>, String>, InspectFn>,
+        zip_cow: crate::ZipCowIter<'a, std::ops::Range<u32>, str>,
+        zip_share_or_clone: crate::ZipShareOrClone<std::ops::Range<u32>, String>,
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_nameable_adapters() {
+        let s = String::from("Hello");
+        let pipelines = Pipelines {
+            zip_clone: crate::zip_clone(0..2, s.clone()),
+            zip_clone_background: crate::zip_clone_background(0..2, s.clone(), 1),
+            zip_clone_batched: crate::zip_clone_batched(0..2, s.clone(), 2),
+            zip_clone_original_by_key: crate::zip_clone_original_by_key(0..2, s.clone(), |&i| i),
+            with_position_clone: crate::with_position_clone(0..2, s.clone()),
+            map_while_clone: crate::map_while_clone(0..2, s.clone(), |i, _| Some(i)),
+            inspect_pair: crate::inspect_pair(crate::zip_clone(0..2, s.clone()), |_, _| {}),
+            zip_cow: crate::zip_cow(0..2, std::borrow::Cow::Borrowed("Hello")),
+            zip_share_or_clone: crate::zip_share_or_clone(0..2, s),
+        };
+        assert_eq!(pipelines.zip_clone.count(), 2);
+    }
+
+    #[test]
+    fn test_rearm() {
+        // An iterator that is not fused, returning `None` between 2 and 3.
+        let mut source = vec![Some(1), Some(2), None, Some(3), Some(4)].into_iter();
+        let iter = std::iter::from_fn(move || source.next().flatten());
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let mut iter = zip_clone(iter, cloned);
+        assert_eq!(
+            iter.by_ref().map(|(item, _)| item).collect::<Vec<_>>(),
+            [1, 2]
+        );
+        assert!(iter.next().is_none());
+        assert_eq!(count.load(Ordering::Relaxed), 1);
+        let cloned = Clonable { count: &count };
+        assert!(iter.rearm(cloned).is_none());
+        assert_eq!(iter.map(|(item, _)| item).collect::<Vec<_>>(), [3, 4]);
+        assert_eq!(count.load(Ordering::Relaxed), 2);
+    }
 }
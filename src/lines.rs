@@ -0,0 +1,138 @@
+use std::io::{self, BufRead};
+
+/// Call a function with each line of a reader and a copy of a value, without
+/// reading ahead of the line being processed.
+///
+/// The next line is only read after the function returns, so a line typed
+/// interactively, or received on a socket, is processed without waiting for
+/// the following line. Lines are passed with their line ending removed.
+///
+/// Without reading ahead, a line is only known to be the last if it ends
+/// without a line ending. That line receives the original value. Every other
+/// line receives a clone, and if the original was not used, it is returned
+/// once the reader reaches the end, so that it can be reused.
+///
+/// Example:
+/// ```rust
+/// use zip_clone::for_each_line_with;
+///
+/// let input = "a\nb\nc";
+/// let mut lines = Vec::new();
+/// let unused = for_each_line_with(input.as_bytes(), String::from(">"), |line, prefix| {
+///     lines.push(prefix + line);
+/// })
+/// .unwrap();
+/// assert_eq!(lines, [">a", ">b", ">c"]);
+/// assert_eq!(unused, None);
+/// ```
+pub fn for_each_line_with<R, C, F>(mut reader: R, value: C, mut f: F) -> io::Result<Option<C>>
+where
+    R: BufRead,
+    C: Clone,
+    F: FnMut(&str, C),
+{
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(Some(value));
+        }
+        if !line.ends_with('\n') {
+            // The line ended at the end of the reader, so it is the last.
+            f(&line, value);
+            return Ok(None);
+        }
+        // As for `BufRead::lines`, strip one `\n`, and one `\r` before it.
+        line.pop();
+        if line.ends_with('\r') {
+            line.pop();
+        }
+        f(&line, value.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{self, BufRead, Read};
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use crate::for_each_line_with;
+    use crate::tests::Clonable;
+
+    // A reader that fails if it is read after the last line is processed.
+    struct Interactive<'a> {
+        lines: &'a [&'a str],
+        processed: &'a AtomicU32,
+        next: usize,
+    }
+
+    impl Read for Interactive<'_> {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            unreachable!()
+        }
+    }
+
+    impl BufRead for Interactive<'_> {
+        fn fill_buf(&mut self) -> io::Result<&[u8]> {
+            assert_eq!(self.processed.load(Ordering::Relaxed) as usize, self.next);
+            Ok(self
+                .lines
+                .get(self.next)
+                .map_or(&[], |line| line.as_bytes()))
+        }
+
+        fn consume(&mut self, amt: usize) {
+            if amt > 0 {
+                assert_eq!(amt, self.lines[self.next].len());
+                self.next += 1;
+            }
+        }
+    }
+
+    #[test]
+    fn test_for_each_line_with() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let processed = AtomicU32::new(0);
+        let reader = Interactive {
+            lines: &["a\n", "b\r\n", "c\n"],
+            processed: &processed,
+            next: 0,
+        };
+        let unused = for_each_line_with(reader, cloned, |_, _| {
+            processed.fetch_add(1, Ordering::Relaxed);
+        })
+        .unwrap();
+        assert!(unused.is_some());
+        assert_eq!(processed.load(Ordering::Relaxed), 3);
+        assert_eq!(count.load(Ordering::Relaxed), 3);
+    }
+
+    #[test]
+    fn test_for_each_line_with_unterminated() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let mut lines = Vec::new();
+        let unused = for_each_line_with("a\n\nb".as_bytes(), cloned, |line, _| {
+            lines.push(line.to_string());
+        })
+        .unwrap();
+        assert!(unused.is_none());
+        assert_eq!(lines, ["a", "", "b"]);
+        assert_eq!(count.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn test_for_each_line_with_carriage_returns() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let mut lines = Vec::new();
+        let unused = for_each_line_with("a\r\r\nb\r".as_bytes(), cloned, |line, _| {
+            lines.push(line.to_string());
+        })
+        .unwrap();
+        assert!(unused.is_none());
+        assert_eq!(lines, ["a\r", "b\r"]);
+        assert_eq!(count.load(Ordering::Relaxed), 1);
+    }
+}
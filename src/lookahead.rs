@@ -0,0 +1,258 @@
+use core::iter::Peekable;
+
+use crate::chain::ChainItems;
+
+// A `Peekable` iterator that can also take back an item at either end.
+//
+// `ZipCloneIter::try_fold` runs one item behind the inner `try_fold`, so when
+// it stops early it has already taken the following item from the underlying
+// iterator. That item is pushed back here, so that it is returned next.
+//
+// The methods are inlined so that the panic-free check in `tests/no_panic.rs`
+// can see through them.
+pub(crate) struct Lookahead<I>
+where
+    I: Iterator,
+{
+    front: Option<I::Item>,
+    iter: Peekable<I>,
+    back: Option<I::Item>,
+}
+
+impl<I> Lookahead<I>
+where
+    I: Iterator,
+{
+    #[inline]
+    pub(crate) fn new(iter: Peekable<I>) -> Self {
+        Lookahead {
+            front: None,
+            iter,
+            back: None,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn peek(&mut self) -> Option<&I::Item> {
+        if self.front.is_some() {
+            return self.front.as_ref();
+        }
+        match self.iter.peek() {
+            Some(item) => Some(item),
+            None => self.back.as_ref(),
+        }
+    }
+
+    // Return an item taken from the front, so that it is returned next.
+    #[cfg(all(feature = "nightly", not(feature = "predictable")))]
+    #[inline]
+    pub(crate) fn push_front(&mut self, item: I::Item) {
+        debug_assert!(self.front.is_none());
+        self.front = Some(item);
+    }
+
+    // Return an item taken from the back, so that it is returned next from the
+    // back.
+    #[cfg(all(feature = "nightly", not(feature = "predictable")))]
+    #[inline]
+    pub(crate) fn push_back(&mut self, item: I::Item) {
+        debug_assert!(self.back.is_none());
+        self.back = Some(item);
+    }
+
+    // The pushed back items have already been taken from the underlying
+    // iterator, so `f` is applied to them now, before any remaining items.
+    #[inline]
+    pub(crate) fn map<B, F>(self, mut f: F) -> Lookahead<core::iter::Map<Peekable<I>, F>>
+    where
+        F: FnMut(I::Item) -> B,
+    {
+        let front = self.front.map(&mut f);
+        let back = self.back.map(&mut f);
+        Lookahead {
+            front,
+            iter: self.iter.map(f).peekable(),
+            back,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn filter<P>(self, mut predicate: P) -> Lookahead<core::iter::Filter<Peekable<I>, P>>
+    where
+        P: FnMut(&I::Item) -> bool,
+    {
+        let front = self.front.filter(&mut predicate);
+        let back = self.back.filter(&mut predicate);
+        Lookahead {
+            front,
+            iter: self.iter.filter(predicate).peekable(),
+            back,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn chain<J>(self, other: J) -> Lookahead<ChainItems<Peekable<I>, J>>
+    where
+        J: Iterator<Item = I::Item>,
+    {
+        Lookahead {
+            front: self.front,
+            iter: ChainItems::with_middle(self.iter, self.back, other).peekable(),
+            back: None,
+        }
+    }
+}
+
+impl<I> Iterator for Lookahead<I>
+where
+    I: Iterator,
+{
+    type Item = I::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.front.take() {
+            Some(item) => Some(item),
+            None => self.iter.next().or_else(|| self.back.take()),
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let extra = self.front.is_some() as usize + self.back.is_some() as usize;
+        let (lower, upper) = self.iter.size_hint();
+        (
+            lower.saturating_add(extra),
+            upper.and_then(|upper| upper.checked_add(extra)),
+        )
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.front.is_some() as usize + self.iter.count() + self.back.is_some() as usize
+    }
+
+    #[inline]
+    fn last(self) -> Option<Self::Item> {
+        let last = self.iter.last();
+        self.back.or(last).or(self.front)
+    }
+
+    #[inline]
+    fn nth(&mut self, mut n: usize) -> Option<Self::Item> {
+        if let Some(item) = self.front.take() {
+            if n == 0 {
+                return Some(item);
+            }
+            n -= 1;
+        }
+        if self.back.is_none() {
+            return self.iter.nth(n);
+        }
+        // The underlying `nth` does not say how many items it skipped, so
+        // step through the items to know whether the item at the back is
+        // reached.
+        for _ in 0..n {
+            self.next()?;
+        }
+        self.next()
+    }
+
+    #[inline]
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        let acc = match self.front {
+            Some(item) => f(init, item),
+            None => init,
+        };
+        let acc = self.iter.fold(acc, &mut f);
+        match self.back {
+            Some(item) => f(acc, item),
+            None => acc,
+        }
+    }
+
+    #[cfg(all(feature = "nightly", not(feature = "predictable")))]
+    #[inline]
+    fn try_fold<B, F, R>(&mut self, init: B, mut f: F) -> R
+    where
+        F: FnMut(B, Self::Item) -> R,
+        R: core::ops::Try<Output = B>,
+    {
+        let acc = match self.front.take() {
+            Some(item) => f(init, item)?,
+            None => init,
+        };
+        let acc = self.iter.try_fold(acc, &mut f)?;
+        match self.back.take() {
+            Some(item) => f(acc, item),
+            None => R::from_output(acc),
+        }
+    }
+}
+
+impl<I> DoubleEndedIterator for Lookahead<I>
+where
+    I: DoubleEndedIterator,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match self.back.take() {
+            Some(item) => Some(item),
+            None => self.iter.next_back().or_else(|| self.front.take()),
+        }
+    }
+
+    #[inline]
+    fn nth_back(&mut self, mut n: usize) -> Option<Self::Item> {
+        if let Some(item) = self.back.take() {
+            if n == 0 {
+                return Some(item);
+            }
+            n -= 1;
+        }
+        if self.front.is_none() {
+            return self.iter.nth_back(n);
+        }
+        for _ in 0..n {
+            self.next_back()?;
+        }
+        self.next_back()
+    }
+
+    #[inline]
+    fn rfold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        let acc = match self.back {
+            Some(item) => f(init, item),
+            None => init,
+        };
+        let acc = self.iter.rfold(acc, &mut f);
+        match self.front {
+            Some(item) => f(acc, item),
+            None => acc,
+        }
+    }
+
+    #[cfg(all(feature = "nightly", not(feature = "predictable")))]
+    #[inline]
+    fn try_rfold<B, F, R>(&mut self, init: B, mut f: F) -> R
+    where
+        F: FnMut(B, Self::Item) -> R,
+        R: core::ops::Try<Output = B>,
+    {
+        let acc = match self.back.take() {
+            Some(item) => f(init, item)?,
+            None => init,
+        };
+        let acc = self.iter.try_rfold(acc, &mut f)?;
+        match self.front.take() {
+            Some(item) => f(acc, item),
+            None => R::from_output(acc),
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use core::iter::FusedIterator;
+
+/// Zip an iterator to clones of a value, cloning for every item.
+///
+/// Unlike [`zip_clone`](crate::zip_clone), this never looks ahead and never
+/// returns the original value, which is dropped with the iterator. Each item
+/// is taken from the underlying iterator only when it is requested, which
+/// suits sources with side effects where the value is cheap to clone. This
+/// behaves like `iter.zip(repeat_with(|| value.clone()))`, but is
+/// double-ended and exact-size when the underlying iterator is.
+///
+/// Example:
+/// ```rust
+/// use zip_clone::zip_clone_lossy;
+///
+/// let mut iter = zip_clone_lossy(0..2, String::from("Hello"));
+/// assert_eq!(iter.next(), Some((0, String::from("Hello"))));
+/// assert_eq!(iter.next(), Some((1, String::from("Hello"))));
+/// assert_eq!(iter.next(), None);
+/// assert_eq!(iter.into_value(), "Hello");
+/// ```
+pub fn zip_clone_lossy<I, C>(iter: I, value: C) -> ZipCloneLossy<I, C>
+where
+    I: Iterator,
+    C: Clone,
+{
+    ZipCloneLossy { iter, value }
+}
+
+/// An iterator that pairs every item with a clone of a value.
+///
+/// This struct is created by [`zip_clone_lossy`].
+#[derive(Debug, Clone)]
+pub struct ZipCloneLossy<I, C> {
+    iter: I,
+    value: C,
+}
+
+impl<I, C> ZipCloneLossy<I, C> {
+    /// Return the value, which was never given to an item.
+    pub fn into_value(self) -> C {
+        self.value
+    }
+}
+
+impl<I, C> Iterator for ZipCloneLossy<I, C>
+where
+    I: Iterator,
+    C: Clone,
+{
+    type Item = (I::Item, C);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.iter.next()?;
+        Some((item, self.value.clone()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let item = self.iter.nth(n)?;
+        Some((item, self.value.clone()))
+    }
+}
+
+impl<I, C> DoubleEndedIterator for ZipCloneLossy<I, C>
+where
+    I: DoubleEndedIterator,
+    C: Clone,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let item = self.iter.next_back()?;
+        Some((item, self.value.clone()))
+    }
+}
+
+impl<I, C> ExactSizeIterator for ZipCloneLossy<I, C>
+where
+    I: ExactSizeIterator,
+    C: Clone,
+{
+}
+
+impl<I, C> FusedIterator for ZipCloneLossy<I, C>
+where
+    I: FusedIterator,
+    C: Clone,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use crate::tests::Clonable;
+    use crate::zip_clone_lossy;
+
+    #[test]
+    fn test_zip_clone_lossy() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let taken = Cell::new(0);
+        let source = (0..5).inspect(|_| taken.set(taken.get() + 1));
+        let mut iter = zip_clone_lossy(source, cloned);
+        assert!(iter.next().is_some());
+        assert_eq!(taken.get(), 1);
+        assert_eq!(iter.map(|_| ()).count(), 4);
+        assert_eq!(count.load(Ordering::Relaxed), 5);
+    }
+}
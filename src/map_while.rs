@@ -0,0 +1,156 @@
+use core::iter::FusedIterator;
+
+/// Map items while a function returns `Some`, pairing each result with clones
+/// of a value.
+///
+/// The function receives each item and a reference to the value. Iteration
+/// ends the first time the function returns `None`. The adapter applies the
+/// function one item ahead, so the last mapped item receives the original
+/// value. Applying [`Iterator::map_while`] after [`zip_clone`](crate::zip_clone)
+/// instead wastes the original on the item that ends iteration.
+///
+/// Example:
+/// ```rust
+/// use zip_clone::map_while_clone;
+///
+/// let words = ["1", "2", "x", "4"];
+/// let parsed = map_while_clone(words.iter(), String::from("Hello"), |word, _| {
+///     word.parse::<u32>().ok()
+/// });
+/// assert_eq!(
+///     parsed.collect::<Vec<_>>(),
+///     [(1, String::from("Hello")), (2, String::from("Hello"))]
+/// );
+/// ```
+pub fn map_while_clone<I, C, T, F>(iter: I, value: C, f: F) -> MapWhileClone<I, C, T, F>
+where
+    I: Iterator,
+    C: Clone,
+    F: FnMut(I::Item, &C) -> Option<T>,
+{
+    MapWhileClone {
+        iter,
+        value: Some(value),
+        lookahead: None,
+        f,
+    }
+}
+
+/// An iterator that maps items while a function returns `Some`, pairing each
+/// result with clones of a value.
+///
+/// This struct is created by [`map_while_clone`].
+pub struct MapWhileClone<I, C, T, F> {
+    iter: I,
+    value: Option<C>,
+    // The mapped next item, if already computed.
+    lookahead: Option<T>,
+    f: F,
+}
+
+impl<I, C, T, F> MapWhileClone<I, C, T, F>
+where
+    I: Iterator,
+    F: FnMut(I::Item, &C) -> Option<T>,
+{
+    fn map_next(&mut self, value: &C) -> Option<T> {
+        let item = self.iter.next()?;
+        (self.f)(item, value)
+    }
+}
+
+impl<I, C, T, F> Iterator for MapWhileClone<I, C, T, F>
+where
+    I: Iterator,
+    C: Clone,
+    F: FnMut(I::Item, &C) -> Option<T>,
+{
+    type Item = (T, C);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let value = self.value.take()?;
+        let mapped = match self.lookahead.take() {
+            Some(mapped) => mapped,
+            // The lookahead is only empty before the first item.
+            None => self.map_next(&value)?,
+        };
+        match self.map_next(&value) {
+            Some(following) => {
+                self.lookahead = Some(following);
+                let cloned = value.clone();
+                self.value = Some(value);
+                Some((mapped, cloned))
+            }
+            None => Some((mapped, value)),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.value.is_none() {
+            return (0, Some(0));
+        }
+        let pending = usize::from(self.lookahead.is_some());
+        let (_, upper) = self.iter.size_hint();
+        (pending, upper.and_then(|upper| upper.checked_add(pending)))
+    }
+}
+
+impl<I, C, T, F> FusedIterator for MapWhileClone<I, C, T, F>
+where
+    I: Iterator,
+    C: Clone,
+    F: FnMut(I::Item, &C) -> Option<T>,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use crate::map_while_clone;
+    use crate::tests::Clonable;
+
+    #[test]
+    fn test_map_while_clone() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let iter = map_while_clone(1..10, cloned, |i, _| Some(i).filter(|&i| i < 5));
+        assert_eq!(iter.map(|(i, _)| i).collect::<Vec<_>>(), [1, 2, 3, 4]);
+        assert_eq!(count.load(Ordering::Relaxed), 3);
+    }
+
+    #[test]
+    fn test_map_while_clone_original_last() {
+        let value = String::from("Hello");
+        let ptr = value.as_ptr();
+        let iter = map_while_clone(1..10, value, |i, _| Some(i).filter(|&i| i < 4));
+        let pairs = iter.collect::<Vec<_>>();
+        assert_eq!(pairs.len(), 3);
+        assert_ne!(pairs[0].1.as_ptr(), ptr);
+        assert_ne!(pairs[1].1.as_ptr(), ptr);
+        assert_eq!(pairs[2].1.as_ptr(), ptr);
+    }
+
+    #[test]
+    fn test_map_while_clone_exhausted() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let mut iter = map_while_clone(1..4, cloned, |i, _| Some(i * 2));
+        assert_eq!(iter.size_hint(), (0, Some(3)));
+        assert_eq!(iter.next().map(|(i, _)| i), Some(2));
+        assert_eq!(iter.size_hint(), (1, Some(2)));
+        assert_eq!(iter.by_ref().map(|(i, _)| i).collect::<Vec<_>>(), [4, 6]);
+        assert!(iter.next().is_none());
+        assert_eq!(count.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn test_map_while_clone_stops_immediately() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let mut iter = map_while_clone(1..4, cloned, |_, _| None::<u32>);
+        assert!(iter.next().is_none());
+        assert_eq!(iter.size_hint(), (0, Some(0)));
+        assert_eq!(count.load(Ordering::Relaxed), 0);
+    }
+}
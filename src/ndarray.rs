@@ -0,0 +1,90 @@
+//! Build and fill [`ndarray`](::ndarray) arrays with clones of a value.
+//!
+//! Unlike [`Array::from_elem`] and [`ArrayBase::fill`], these helpers
+//! guarantee that an array of `len` elements uses `len - 1` clones, with the
+//! last element receiving the original value.
+
+use ::ndarray::{Array, ArrayBase, DataMut, Dimension, ShapeBuilder};
+
+use crate::{fill_from_prototype, repeat_clones};
+
+/// Create an array of the given shape with every element a copy of a value.
+///
+/// **Panics** if the number of elements overflows `isize`.
+///
+/// Example:
+/// ```rust
+/// use zip_clone::ndarray::array_of_clones;
+///
+/// let a = array_of_clones((2, 3), String::from("."));
+/// assert_eq!(a.shape(), [2, 3]);
+/// assert_eq!(a[[1, 2]], ".");
+/// ```
+pub fn array_of_clones<C, Sh, D>(shape: Sh, value: C) -> Array<C, D>
+where
+    C: Clone,
+    Sh: ShapeBuilder<Dim = D>,
+    D: Dimension,
+{
+    let shape = shape.into_shape_with_order();
+    let cells = repeat_clones(value, shape.size()).collect();
+    Array::from_shape_vec(shape, cells).expect("array size overflow")
+}
+
+/// Set every element of an array to a copy of a value.
+///
+/// Existing elements are updated using [`Clone::clone_from`], so they can
+/// reuse their resources. The last element receives the original value.
+/// Returns the number of elements set.
+///
+/// Example:
+/// ```rust
+/// use ndarray::Array2;
+/// use zip_clone::ndarray::fill_with_clones;
+///
+/// let mut a = Array2::from_elem((2, 2), String::with_capacity(16));
+/// assert_eq!(fill_with_clones(&mut a, String::from("Hello")), 4);
+/// assert!(a.iter().all(|s| s == "Hello"));
+/// ```
+pub fn fill_with_clones<C, S, D>(array: &mut ArrayBase<S, D>, value: C) -> usize
+where
+    C: Clone,
+    S: DataMut<Elem = C>,
+    D: Dimension,
+{
+    fill_from_prototype(array.iter_mut(), value)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use super::{array_of_clones, fill_with_clones};
+    use crate::tests::Clonable;
+
+    #[test]
+    fn test_array_of_clones() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let a = array_of_clones((2, 3, 4), cloned);
+        assert_eq!(a.len(), 24);
+        assert_eq!(count.load(Ordering::Relaxed), 23);
+    }
+
+    #[test]
+    fn test_array_of_clones_empty() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let a = array_of_clones((2, 0), cloned);
+        assert!(a.is_empty());
+        assert_eq!(count.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn test_fill_with_clones() {
+        let mut a = ::ndarray::Array2::<u32>::zeros((3, 3));
+        let mut column = a.column_mut(1);
+        assert_eq!(fill_with_clones(&mut column, 7), 3);
+        assert_eq!(a.sum(), 21);
+    }
+}
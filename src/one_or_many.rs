@@ -0,0 +1,124 @@
+use alloc::vec::Vec;
+
+use crate::zip_clone;
+
+/// Results of mapping an iterator that stores a single result without
+/// allocating.
+///
+/// This enum is created by [`map_one_or_many`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum OneOrMany<T> {
+    /// The iterator yielded exactly one item.
+    One(T),
+    /// The iterator yielded no items, or more than one item.
+    Many(Vec<T>),
+}
+
+impl<T> OneOrMany<T> {
+    /// Return the number of results.
+    pub fn len(&self) -> usize {
+        match self {
+            OneOrMany::One(_) => 1,
+            OneOrMany::Many(v) => v.len(),
+        }
+    }
+
+    /// Return `true` if there are no results.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Return the results as a slice.
+    pub fn as_slice(&self) -> &[T] {
+        match self {
+            OneOrMany::One(one) => core::slice::from_ref(one),
+            OneOrMany::Many(v) => v,
+        }
+    }
+
+    /// Convert into a `Vec`, allocating if there is one result.
+    pub fn into_vec(self) -> Vec<T> {
+        match self {
+            OneOrMany::One(one) => alloc::vec![one],
+            OneOrMany::Many(v) => v,
+        }
+    }
+}
+
+impl<T> From<OneOrMany<T>> for Vec<T> {
+    fn from(results: OneOrMany<T>) -> Self {
+        results.into_vec()
+    }
+}
+
+/// Map each item, together with a copy of a value, storing a single result
+/// without allocating.
+///
+/// If the iterator yields exactly one item, the function receives the
+/// original value and the result is returned as [`OneOrMany::One`], with no
+/// clones and no allocation. Otherwise the results are collected into a `Vec`,
+/// using one fewer clones than items.
+///
+/// Example:
+/// ```rust
+/// use zip_clone::{map_one_or_many, OneOrMany};
+///
+/// let results = map_one_or_many(["a"].iter(), String::from("Hello"), |item, s| s + item);
+/// assert_eq!(results, OneOrMany::One(String::from("Helloa")));
+///
+/// let results = map_one_or_many(["a", "b"].iter(), String::from("Hello"), |item, s| s + item);
+/// assert_eq!(results.as_slice(), ["Helloa", "Hellob"]);
+/// ```
+pub fn map_one_or_many<I, C, R, F>(iter: I, value: C, mut f: F) -> OneOrMany<R>
+where
+    I: Iterator,
+    C: Clone,
+    F: FnMut(I::Item, C) -> R,
+{
+    let mut iter = zip_clone(iter, value).map(|(item, value)| f(item, value));
+    let first = match iter.next() {
+        Some(first) => first,
+        None => return OneOrMany::Many(Vec::new()),
+    };
+    let second = match iter.next() {
+        Some(second) => second,
+        None => return OneOrMany::One(first),
+    };
+    let mut results = Vec::with_capacity(iter.size_hint().0.saturating_add(2));
+    results.push(first);
+    results.push(second);
+    results.extend(iter);
+    OneOrMany::Many(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use crate::tests::Clonable;
+    use crate::{map_one_or_many, OneOrMany, ZipClone};
+
+    #[test]
+    fn test_map_one_or_many_one() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let results = (0..1).map_one_or_many(cloned, |i, _| i);
+        assert_eq!(results, OneOrMany::One(0));
+        assert_eq!(count.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn test_map_one_or_many_many() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let results = map_one_or_many(0..5, cloned, |i, _| i);
+        assert_eq!(results, OneOrMany::Many(vec![0, 1, 2, 3, 4]));
+        assert_eq!(count.load(Ordering::Relaxed), 4);
+    }
+
+    #[test]
+    fn test_map_one_or_many_empty() {
+        let results = map_one_or_many(0..0, String::new(), |i, _| i);
+        assert!(results.is_empty());
+    }
+}
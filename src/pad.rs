@@ -0,0 +1,118 @@
+use core::iter::{Fuse, FusedIterator};
+
+/// Return the items of an iterator, followed by copies of a value until at
+/// least `min_len` items have been returned.
+///
+/// The value is cloned for each padding item except the last, which receives
+/// the original. If the iterator returns at least `min_len` items, the value
+/// is dropped without being cloned. Unlike
+/// `iter.chain(repeat(value).take(k))`, this makes no unused clone and keeps
+/// an exact size for an `ExactSizeIterator`.
+///
+/// Example:
+/// ```rust
+/// use zip_clone::pad_with_clones;
+///
+/// let words = vec![String::from("a"), String::from("b")];
+/// let padded = pad_with_clones(words.into_iter(), 4, String::from("-"));
+/// assert_eq!(padded.len(), 4);
+/// assert_eq!(padded.collect::<Vec<_>>(), ["a", "b", "-", "-"]);
+/// ```
+pub fn pad_with_clones<I>(iter: I, min_len: usize, value: I::Item) -> PadWithClones<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    PadWithClones {
+        iter: iter.fuse(),
+        value: Some(value),
+        returned: 0,
+        min_len,
+    }
+}
+
+/// An iterator that pads the items of an iterator with copies of a value.
+///
+/// This struct is created by [`pad_with_clones`].
+pub struct PadWithClones<I>
+where
+    I: Iterator,
+{
+    iter: Fuse<I>,
+    value: Option<I::Item>,
+    returned: usize,
+    min_len: usize,
+}
+
+impl<I> Iterator for PadWithClones<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(item) = self.iter.next() {
+            self.returned = self.returned.saturating_add(1);
+            return Some(item);
+        }
+        if self.returned >= self.min_len {
+            self.value = None;
+            return None;
+        }
+        self.returned += 1;
+        if self.returned == self.min_len {
+            self.value.take()
+        } else {
+            self.value.clone()
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let pad = self.min_len.saturating_sub(self.returned);
+        let (lower, upper) = self.iter.size_hint();
+        (lower.max(pad), upper.map(|upper| upper.max(pad)))
+    }
+}
+
+impl<I> ExactSizeIterator for PadWithClones<I>
+where
+    I: ExactSizeIterator,
+    I::Item: Clone,
+{
+}
+
+impl<I> FusedIterator for PadWithClones<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use crate::pad_with_clones;
+    use crate::tests::Clonable;
+
+    #[test]
+    fn test_pad_with_clones() {
+        let count = AtomicU32::new(0);
+        let items = vec![Clonable { count: &count }, Clonable { count: &count }];
+        let padded = pad_with_clones(items.into_iter(), 5, Clonable { count: &count });
+        assert_eq!(padded.len(), 5);
+        assert_eq!(padded.map(|_| ()).count(), 5);
+        assert_eq!(count.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn test_pad_with_clones_long_enough() {
+        let count = AtomicU32::new(0);
+        let items = vec![Clonable { count: &count }, Clonable { count: &count }];
+        let padded = pad_with_clones(items.into_iter(), 1, Clonable { count: &count });
+        assert_eq!(padded.len(), 2);
+        assert_eq!(padded.map(|_| ()).count(), 2);
+        assert_eq!(count.load(Ordering::Relaxed), 0);
+    }
+}
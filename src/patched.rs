@@ -0,0 +1,167 @@
+use core::iter::FusedIterator;
+
+use crate::{zip_clone, ZipCloneIter};
+
+/// A change that can be applied to a value in place.
+///
+/// This is implemented for closures that take `&mut C`. Implement it for
+/// other types to use them as patches with [`zip_patched`].
+pub trait Patch<C> {
+    /// Apply the change to the value.
+    fn apply(self, value: &mut C);
+}
+
+impl<C, F> Patch<C> for F
+where
+    F: FnOnce(&mut C),
+{
+    fn apply(self, value: &mut C) {
+        self(value)
+    }
+}
+
+/// Return a patched copy of a base value for each patch.
+///
+/// Each patch except the last is applied to a clone of the base, and the last
+/// patch is applied to the base itself, so `n` patches use `n - 1` clones.
+///
+/// Example:
+/// ```rust
+/// use zip_clone::zip_patched;
+///
+/// #[derive(Clone)]
+/// struct Config {
+///     host: String,
+///     port: u16,
+/// }
+///
+/// let base = Config {
+///     host: String::from("localhost"),
+///     port: 80,
+/// };
+/// let patches: Vec<Box<dyn FnOnce(&mut Config)>> = vec![
+///     Box::new(|c| c.port = 8080),
+///     Box::new(|c| c.host = String::from("example.com")),
+/// ];
+/// let configs = zip_patched(base, patches).collect::<Vec<_>>();
+/// assert_eq!(configs[0].port, 8080);
+/// assert_eq!(configs[1].host, "example.com");
+/// assert_eq!(configs[1].port, 80);
+/// ```
+pub fn zip_patched<C, P>(base: C, patches: P) -> ZipPatched<P::IntoIter, C>
+where
+    C: Clone,
+    P: IntoIterator,
+    P::Item: Patch<C>,
+{
+    ZipPatched {
+        inner: zip_clone(patches.into_iter(), base),
+    }
+}
+
+/// An iterator that applies each patch to a copy of a base value.
+///
+/// This struct is created by [`zip_patched`].
+pub struct ZipPatched<I, C>
+where
+    I: Iterator,
+{
+    inner: ZipCloneIter<I, C>,
+}
+
+impl<I, C> Iterator for ZipPatched<I, C>
+where
+    I: Iterator,
+    I::Item: Patch<C>,
+    C: Clone,
+{
+    type Item = C;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (patch, mut value) = self.inner.next()?;
+        patch.apply(&mut value);
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+
+    fn count(self) -> usize
+    where
+        Self: Sized,
+    {
+        self.inner.count()
+    }
+
+    fn last(self) -> Option<Self::Item>
+    where
+        Self: Sized,
+    {
+        let (patch, mut value) = self.inner.last()?;
+        patch.apply(&mut value);
+        Some(value)
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let (patch, mut value) = self.inner.nth(n)?;
+        patch.apply(&mut value);
+        Some(value)
+    }
+}
+
+impl<I, C> DoubleEndedIterator for ZipPatched<I, C>
+where
+    I: DoubleEndedIterator,
+    I::Item: Patch<C>,
+    C: Clone,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let (patch, mut value) = self.inner.next_back()?;
+        patch.apply(&mut value);
+        Some(value)
+    }
+}
+
+impl<I, C> ExactSizeIterator for ZipPatched<I, C>
+where
+    I: ExactSizeIterator,
+    I::Item: Patch<C>,
+    C: Clone,
+{
+}
+
+impl<I, C> FusedIterator for ZipPatched<I, C>
+where
+    I: Iterator,
+    I::Item: Patch<C>,
+    C: Clone,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use crate::tests::Clonable;
+    use crate::{zip_patched, Patch};
+
+    // A patch value that increments the number paired with the clone.
+    struct Bump;
+
+    impl<'a> Patch<(u32, Clonable<'a>)> for Bump {
+        fn apply(self, value: &mut (u32, Clonable<'a>)) {
+            value.0 += 1;
+        }
+    }
+
+    #[test]
+    fn test_zip_patched() {
+        let count = AtomicU32::new(0);
+        let base = (0, Clonable { count: &count });
+        let patched = zip_patched(base, vec![Bump, Bump, Bump]);
+        assert_eq!(patched.len(), 3);
+        assert!(patched.map(|(n, _)| n).all(|n| n == 1));
+        assert_eq!(count.load(Ordering::Relaxed), 2);
+    }
+}
@@ -0,0 +1,147 @@
+use core::iter::FusedIterator;
+
+use crate::{zip_clone, ZipCloneIter};
+
+/// The position of an item in an iterator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Position {
+    /// The first of several items.
+    First,
+    /// An item that is neither first nor last.
+    Middle,
+    /// The last of several items.
+    Last,
+    /// The only item.
+    Only,
+}
+
+/// Zip an iterator to a repeatedly cloned value, tagging each item with its
+/// position.
+///
+/// The position is taken from the lookahead that [`zip_clone`] already uses to
+/// decide whether to clone the value, so no extra peeking is needed. As with
+/// `zip_clone`, the first item, tagged [`Position::First`] or
+/// [`Position::Only`], receives the original value.
+///
+/// Example:
+/// ```rust
+/// use zip_clone::{with_position_clone, Position};
+///
+/// let mut line = String::new();
+/// let words = ["a", "b", "c"];
+/// for (position, word, mut sep) in with_position_clone(words.iter(), String::from(", ")) {
+///     if let Position::Last | Position::Only = position {
+///         sep.clear();
+///     }
+///     line.push_str(word);
+///     line.push_str(&sep);
+/// }
+/// assert_eq!(line, "a, b, c");
+/// ```
+pub fn with_position_clone<I, C>(iter: I, value: C) -> WithPositionClone<I, C>
+where
+    I: Iterator,
+    C: Clone,
+{
+    WithPositionClone {
+        inner: zip_clone(iter, value),
+        first: true,
+    }
+}
+
+/// An iterator that pairs items with clones of a value and their position.
+///
+/// This struct is created by [`with_position_clone`].
+pub struct WithPositionClone<I, C>
+where
+    I: Iterator,
+{
+    inner: ZipCloneIter<I, C>,
+    first: bool,
+}
+
+impl<I, C> Iterator for WithPositionClone<I, C>
+where
+    I: Iterator,
+    C: Clone,
+{
+    type Item = (Position, I::Item, C);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (item, cloned) = self.inner.next()?;
+        let last = self.inner.iter.peek().is_none();
+        let position = match (self.first, last) {
+            (true, true) => Position::Only,
+            (true, false) => Position::First,
+            (false, true) => Position::Last,
+            (false, false) => Position::Middle,
+        };
+        self.first = false;
+        Some((position, item, cloned))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+
+    fn count(self) -> usize
+    where
+        Self: Sized,
+    {
+        self.inner.count()
+    }
+}
+
+impl<I, C> ExactSizeIterator for WithPositionClone<I, C>
+where
+    I: ExactSizeIterator,
+    C: Clone,
+{
+}
+
+impl<I, C> FusedIterator for WithPositionClone<I, C>
+where
+    I: Iterator,
+    C: Clone,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use crate::tests::Clonable;
+    use crate::{with_position_clone, Position};
+
+    #[test]
+    fn test_with_position_clone() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let positions = with_position_clone(1..5, cloned)
+            .map(|(position, item, _)| (position, item))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            positions,
+            [
+                (Position::First, 1),
+                (Position::Middle, 2),
+                (Position::Middle, 3),
+                (Position::Last, 4)
+            ]
+        );
+        assert_eq!(count.load(Ordering::Relaxed), 3);
+    }
+
+    #[test]
+    fn test_with_position_clone_only() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let mut iter = with_position_clone(Some(1).into_iter(), cloned);
+        assert_eq!(
+            iter.next().map(|(position, ..)| position),
+            Some(Position::Only)
+        );
+        assert!(iter.next().is_none());
+        assert_eq!(count.load(Ordering::Relaxed), 0);
+    }
+}
@@ -0,0 +1,79 @@
+/// A value from which copies are made on demand.
+///
+/// Each call to [`stamp`](Prototype::stamp) returns a clone of the value.
+/// When no more copies are needed, [`into_inner`](Prototype::into_inner)
+/// returns the original value without cloning it.
+///
+/// Example:
+/// ```rust
+/// use zip_clone::Prototype;
+///
+/// let prototype = Prototype::new(String::from("Hello"));
+/// let first = prototype.stamp();
+/// let second = prototype.stamp();
+/// let last = prototype.into_inner();
+/// assert_eq!([first, second, last], ["Hello", "Hello", "Hello"]);
+/// ```
+#[derive(Debug)]
+pub struct Prototype<T> {
+    value: T,
+}
+
+impl<T> Prototype<T> {
+    /// Create a prototype from a value.
+    pub fn new(value: T) -> Self {
+        Prototype { value }
+    }
+
+    /// Return a reference to the value.
+    pub fn get(&self) -> &T {
+        &self.value
+    }
+
+    /// Return the original value.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T> Prototype<T>
+where
+    T: Clone,
+{
+    /// Return a clone of the value.
+    pub fn stamp(&self) -> T {
+        self.value.clone()
+    }
+
+    /// Return a vector containing `n` clones of the value.
+    #[cfg(feature = "alloc")]
+    pub fn stamp_n(&self, n: usize) -> alloc::vec::Vec<T> {
+        core::iter::repeat_with(|| self.value.clone())
+            .take(n)
+            .collect()
+    }
+}
+
+impl<T> From<T> for Prototype<T> {
+    fn from(value: T) -> Self {
+        Prototype::new(value)
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use crate::tests::Clonable;
+    use crate::Prototype;
+
+    #[test]
+    fn test_prototype() {
+        let count = AtomicU32::new(0);
+        let prototype = Prototype::new(Clonable { count: &count });
+        let _first = prototype.stamp();
+        assert_eq!(prototype.stamp_n(3).len(), 3);
+        let _last = prototype.into_inner();
+        assert_eq!(count.load(Ordering::Relaxed), 4);
+    }
+}
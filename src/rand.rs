@@ -0,0 +1,120 @@
+//! Pair items with random number generators forked from a master generator.
+//!
+//! Cloning a generator copies its state, so every clone produces the same
+//! sequence of numbers. These helpers instead seed a new generator for each
+//! item from the output of the master, so the streams are independent, while
+//! the master itself is handed to the last item.
+
+use core::iter::{FusedIterator, Peekable};
+
+use ::rand_core::{RngCore, SeedableRng};
+
+/// Zip an iterator to random number generators forked from a master.
+///
+/// Each item except the last is paired with a new generator seeded from the
+/// master's output. The last item receives the master. The sequence of
+/// generators is determined by the master's state, so a seeded master gives
+/// reproducible results.
+///
+/// Example:
+/// ```rust
+/// use rand_core::{RngCore, SeedableRng};
+/// use rand_xorshift::XorShiftRng;
+/// use zip_clone::rand::zip_fork_rng;
+///
+/// let master = XorShiftRng::seed_from_u64(42);
+/// let samples = zip_fork_rng(0..3, master)
+///     .map(|(_, mut rng)| rng.next_u64())
+///     .collect::<Vec<_>>();
+/// assert_ne!(samples[0], samples[1]);
+/// ```
+pub fn zip_fork_rng<I, R>(iter: I, master: R) -> ZipForkRng<I, R>
+where
+    I: Iterator,
+    R: RngCore + SeedableRng,
+{
+    ZipForkRng {
+        iter: iter.peekable(),
+        master: Some(master),
+    }
+}
+
+/// An iterator that pairs items with forked random number generators.
+///
+/// This struct is created by [`zip_fork_rng`].
+pub struct ZipForkRng<I, R>
+where
+    I: Iterator,
+{
+    iter: Peekable<I>,
+    master: Option<R>,
+}
+
+impl<I, R> Iterator for ZipForkRng<I, R>
+where
+    I: Iterator,
+    R: RngCore + SeedableRng,
+{
+    type Item = (I::Item, R);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.iter.next()?;
+        let rng = if self.iter.peek().is_some() {
+            let master = self.master.as_mut()?;
+            let mut seed = R::Seed::default();
+            master.fill_bytes(seed.as_mut());
+            R::from_seed(seed)
+        } else {
+            self.master.take()?
+        };
+        Some((item, rng))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.master.is_none() {
+            (0, Some(0))
+        } else {
+            self.iter.size_hint()
+        }
+    }
+}
+
+impl<I, R> ExactSizeIterator for ZipForkRng<I, R>
+where
+    I: ExactSizeIterator,
+    R: RngCore + SeedableRng,
+{
+}
+
+impl<I, R> FusedIterator for ZipForkRng<I, R>
+where
+    I: Iterator,
+    R: RngCore + SeedableRng,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use ::rand_core::{RngCore, SeedableRng};
+    use rand_xorshift::XorShiftRng;
+
+    use super::zip_fork_rng;
+
+    #[test]
+    fn test_zip_fork_rng() {
+        let master = XorShiftRng::seed_from_u64(7);
+        let mut expected = master.clone();
+        let mut rngs = zip_fork_rng(0..3, master)
+            .map(|(_, rng)| rng)
+            .collect::<Vec<_>>();
+        assert_eq!(rngs.len(), 3);
+        let first = rngs[0].next_u64();
+        assert_ne!(first, rngs[1].next_u64());
+        // The master forked two children, then was given to the last item.
+        let mut seed = [0; 16];
+        expected.fill_bytes(&mut seed);
+        assert_eq!(first, XorShiftRng::from_seed(seed).next_u64());
+        expected.fill_bytes(&mut seed);
+        assert_eq!(rngs[2].next_u64(), expected.next_u64());
+    }
+}
@@ -0,0 +1,367 @@
+//! Share clones of a value between [`rayon`](::rayon) worker threads.
+//!
+//! Pipelines that use [`par_bridge`](::rayon::iter::ParallelBridge::par_bridge)
+//! cannot use [`zip_clone`](crate::zip_clone) to hand each item a value, since
+//! items are distributed between workers after being zipped. Instead, each
+//! worker keeps its own clone of the value, so a pipeline makes at most one
+//! clone per worker thread rather than one per item.
+//!
+//! A [`ZipCloneIter`] can also be converted into a parallel iterator using
+//! [`IntoParallelIterator`].
+
+use std::sync::Mutex;
+
+use ::rayon::iter::plumbing::{Consumer, Folder, UnindexedConsumer};
+use ::rayon::iter::{IntoParallelIterator, ParallelBridge, ParallelIterator};
+
+use crate::ZipCloneIter;
+
+/// A value with a cached clone for each worker thread of a rayon pool.
+///
+/// Clones are made the first time each worker calls [`with`](Self::with) and
+/// are reused by later calls on the same worker. Replacing the value with
+/// [`set`](Self::set) discards the cached clones, so each worker clones the
+/// new value when it next needs it.
+///
+/// Example:
+/// ```rust
+/// use rayon::prelude::*;
+/// use zip_clone::rayon::WorkerClones;
+///
+/// let buffer = WorkerClones::new(Vec::<u8>::with_capacity(4096));
+/// let total: usize = (0..100).into_par_iter().map(|i| {
+///     buffer.with(|buffer| {
+///         buffer.clear();
+///         buffer.extend(format!("{}", i).bytes());
+///         buffer.len()
+///     })
+/// }).sum();
+/// assert_eq!(total, 190);
+/// ```
+#[derive(Debug)]
+pub struct WorkerClones<C> {
+    prototype: C,
+    slots: Vec<Mutex<Option<C>>>,
+}
+
+impl<C> WorkerClones<C>
+where
+    C: Clone,
+{
+    /// Create a cache of clones for the workers of the current rayon pool.
+    pub fn new(value: C) -> Self {
+        let slots = (0..::rayon::current_num_threads())
+            .map(|_| Mutex::new(None))
+            .collect();
+        WorkerClones {
+            prototype: value,
+            slots,
+        }
+    }
+
+    /// Call a function with the current worker's clone of the value.
+    ///
+    /// If called from outside the pool, or re-entrantly from a worker that is
+    /// already using its clone, the function is given a temporary clone.
+    pub fn with<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&mut C) -> R,
+    {
+        let slot = ::rayon::current_thread_index()
+            .and_then(|index| self.slots.get(index))
+            .and_then(|slot| slot.try_lock().ok());
+        match slot {
+            Some(mut slot) => {
+                let cloned = slot.get_or_insert_with(|| self.prototype.clone());
+                f(cloned)
+            }
+            None => f(&mut self.prototype.clone()),
+        }
+    }
+
+    /// Return a reference to the value.
+    pub fn get(&self) -> &C {
+        &self.prototype
+    }
+
+    /// Replace the value, discarding the cached clones of the old value.
+    ///
+    /// Returns the old value.
+    pub fn set(&mut self, value: C) -> C {
+        for slot in &mut self.slots {
+            *slot.get_mut().unwrap_or_else(|e| e.into_inner()) = None;
+        }
+        std::mem::replace(&mut self.prototype, value)
+    }
+
+    /// Return the value, discarding the cached clones.
+    pub fn into_inner(self) -> C {
+        self.prototype
+    }
+}
+
+/// Call a function for each item of an iterator, bridged to rayon, with a
+/// per-worker clone of a value.
+///
+/// At most one clone is made for each worker thread, instead of one for each
+/// item. The original value is returned once all items have been processed.
+///
+/// Example:
+/// ```rust
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use zip_clone::rayon::par_bridge_for_each_clone;
+///
+/// let total = AtomicUsize::new(0);
+/// let s = par_bridge_for_each_clone(0..100, String::new(), |i, s| {
+///     s.clear();
+///     s.push_str(&i.to_string());
+///     total.fetch_add(s.len(), Ordering::Relaxed);
+/// });
+/// assert_eq!(total.into_inner(), 190);
+/// assert!(s.is_empty());
+/// ```
+pub fn par_bridge_for_each_clone<I, C, F>(iter: I, value: C, f: F) -> C
+where
+    I: Iterator + Send,
+    I::Item: Send,
+    C: Clone + Send + Sync,
+    F: Fn(I::Item, &mut C) + Sync + Send,
+{
+    let clones = WorkerClones::new(value);
+    iter.par_bridge()
+        .for_each(|item| clones.with(|cloned| f(item, cloned)));
+    clones.into_inner()
+}
+
+/// Convert the iterator into a parallel iterator, using
+/// [`par_bridge`](::rayon::iter::ParallelBridge::par_bridge) to distribute the
+/// underlying items.
+///
+/// Each split of the work receives its own clone of the value, and pairs its
+/// items with it as [`zip_clone`](crate::zip_clone) does, so the last item of
+/// each split receives the split's value. This uses one more clone per split
+/// than the sequential iterator.
+///
+/// Example:
+/// ```rust
+/// use rayon::prelude::*;
+/// use zip_clone::ZipClone;
+///
+/// let total: usize = (0..100)
+///     .zip_clone(String::from("Hello"))
+///     .into_par_iter()
+///     .map(|(_, s)| s.len())
+///     .sum();
+/// assert_eq!(total, 500);
+/// ```
+impl<I, C> IntoParallelIterator for ZipCloneIter<I, C>
+where
+    I: Iterator + Send,
+    I::Item: Send,
+    C: Clone + Send,
+{
+    type Iter = ParZipClone<I, C>;
+    type Item = (I::Item, C);
+
+    fn into_par_iter(self) -> Self::Iter {
+        ParZipClone { inner: self }
+    }
+}
+
+/// A parallel iterator that pairs items with clones of a value.
+///
+/// This struct is created by converting a [`ZipCloneIter`] using
+/// [`IntoParallelIterator`].
+pub struct ParZipClone<I, C>
+where
+    I: Iterator,
+{
+    inner: ZipCloneIter<I, C>,
+}
+
+impl<I, C> ParallelIterator for ParZipClone<I, C>
+where
+    I: Iterator + Send,
+    I::Item: Send,
+    C: Clone + Send,
+{
+    type Item = (I::Item, C);
+
+    fn drive_unindexed<CB>(mut self, consumer: CB) -> CB::Result
+    where
+        CB: UnindexedConsumer<Self::Item>,
+    {
+        match self.inner.cloned.take() {
+            Some(value) => self
+                .inner
+                .iter
+                .par_bridge()
+                .drive_unindexed(ZipCloneConsumer {
+                    base: consumer,
+                    value,
+                }),
+            None => consumer.into_folder().complete(),
+        }
+    }
+}
+
+// A consumer that pairs items with clones of a value, holding its own value
+// for each split.
+struct ZipCloneConsumer<CB, C> {
+    base: CB,
+    value: C,
+}
+
+impl<T, CB, C> Consumer<T> for ZipCloneConsumer<CB, C>
+where
+    CB: Consumer<(T, C)>,
+    C: Clone + Send,
+{
+    type Folder = ZipCloneFolder<T, CB::Folder, C>;
+    type Reducer = CB::Reducer;
+    type Result = CB::Result;
+
+    fn split_at(self, index: usize) -> (Self, Self, Self::Reducer) {
+        let (left, right, reducer) = self.base.split_at(index);
+        let left = ZipCloneConsumer {
+            base: left,
+            value: self.value.clone(),
+        };
+        let right = ZipCloneConsumer {
+            base: right,
+            value: self.value,
+        };
+        (left, right, reducer)
+    }
+
+    fn into_folder(self) -> Self::Folder {
+        ZipCloneFolder {
+            base: self.base.into_folder(),
+            value: self.value,
+            pending: None,
+        }
+    }
+
+    fn full(&self) -> bool {
+        self.base.full()
+    }
+}
+
+impl<T, CB, C> UnindexedConsumer<T> for ZipCloneConsumer<CB, C>
+where
+    CB: UnindexedConsumer<(T, C)>,
+    C: Clone + Send,
+{
+    fn split_off_left(&self) -> Self {
+        ZipCloneConsumer {
+            base: self.base.split_off_left(),
+            value: self.value.clone(),
+        }
+    }
+
+    fn to_reducer(&self) -> Self::Reducer {
+        self.base.to_reducer()
+    }
+}
+
+// A folder that holds back one item, so that the last item it receives can be
+// paired with the original value.
+struct ZipCloneFolder<T, F, C> {
+    base: F,
+    value: C,
+    pending: Option<T>,
+}
+
+impl<T, F, C> Folder<T> for ZipCloneFolder<T, F, C>
+where
+    F: Folder<(T, C)>,
+    C: Clone,
+{
+    type Result = F::Result;
+
+    fn consume(mut self, item: T) -> Self {
+        if let Some(pending) = self.pending.replace(item) {
+            self.base = self.base.consume((pending, self.value.clone()));
+        }
+        self
+    }
+
+    fn complete(self) -> Self::Result {
+        match self.pending {
+            Some(pending) => self.base.consume((pending, self.value)).complete(),
+            None => self.base.complete(),
+        }
+    }
+
+    fn full(&self) -> bool {
+        self.base.full()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use ::rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+    use super::{par_bridge_for_each_clone, WorkerClones};
+    use crate::tests::Clonable;
+    use crate::ZipClone;
+
+    #[test]
+    fn test_par_bridge_for_each_clone() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let items = AtomicU32::new(0);
+        par_bridge_for_each_clone(0..1000, cloned, |_, _| {
+            items.fetch_add(1, Ordering::Relaxed);
+        });
+        assert_eq!(items.load(Ordering::Relaxed), 1000);
+        assert!(count.load(Ordering::Relaxed) as usize <= ::rayon::current_num_threads());
+    }
+
+    #[test]
+    fn test_worker_clones_set() {
+        let pool = ::rayon::ThreadPoolBuilder::new()
+            .num_threads(1)
+            .build()
+            .unwrap();
+        pool.install(|| {
+            let mut clones = WorkerClones::new(String::from("Hello"));
+            let with = |clones: &WorkerClones<String>| {
+                ::rayon::scope(|_| clones.with(|s| s.push('!')));
+                clones.with(|s| s.clone())
+            };
+            assert_eq!(with(&clones), "Hello!");
+            assert_eq!(with(&clones), "Hello!!");
+            assert_eq!(clones.set(String::from("World")), "Hello");
+            assert_eq!(with(&clones), "World!");
+        });
+    }
+
+    #[test]
+    fn test_into_par_iter() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let mut items = (0..1000)
+            .zip_clone(cloned)
+            .into_par_iter()
+            .map(|(i, _)| i)
+            .collect::<Vec<_>>();
+        items.sort_unstable();
+        assert_eq!(items, (0..1000).collect::<Vec<_>>());
+        // `par_bridge` splits the work at most once per thread.
+        let max = 999 + ::rayon::current_num_threads();
+        assert!(count.load(Ordering::Relaxed) as usize <= max);
+    }
+
+    #[test]
+    fn test_into_par_iter_after_last() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let mut iter = (0..2).zip_clone(cloned);
+        iter.by_ref().for_each(drop);
+        assert_eq!(iter.into_par_iter().count(), 0);
+        assert_eq!(count.load(Ordering::Relaxed), 1);
+    }
+}
@@ -0,0 +1,143 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::iter::{repeat, Repeat, Take};
+use core::ops::Index;
+
+#[cfg(feature = "alloc")]
+use crate::repeat_clones;
+
+/// A read-only view of one value repeated `len` times.
+///
+/// Elements are read without cloning the value. When owned elements are
+/// needed, [`into_vec`](Self::into_vec) creates a `Vec` using `len - 1`
+/// clones, with the original value as the last element.
+///
+/// Example:
+/// ```rust
+/// use zip_clone::RepeatSlice;
+///
+/// let s = RepeatSlice::new(String::from("Hello"), 3);
+/// assert_eq!(s.len(), 3);
+/// assert_eq!(s[2], "Hello");
+/// assert!(s.iter().all(|item| item == "Hello"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RepeatSlice<T> {
+    value: T,
+    len: usize,
+}
+
+impl<T> RepeatSlice<T> {
+    /// Create a view of a value repeated `len` times.
+    pub fn new(value: T, len: usize) -> Self {
+        RepeatSlice { value, len }
+    }
+
+    /// Return the number of elements.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Return `true` if the view has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Return a reference to an element, or `None` if the index is out of
+    /// bounds.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index < self.len {
+            Some(&self.value)
+        } else {
+            None
+        }
+    }
+
+    /// Return an iterator over references to the elements.
+    pub fn iter(&self) -> Take<Repeat<&T>> {
+        repeat(&self.value).take(self.len)
+    }
+
+    /// Return the repeated value.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+
+    /// Convert into a `Vec`, using one fewer clones than elements.
+    ///
+    /// Example:
+    /// ```rust
+    /// use zip_clone::RepeatSlice;
+    ///
+    /// let s = RepeatSlice::new(String::from("Hello"), 3);
+    /// assert_eq!(s.into_vec(), ["Hello", "Hello", "Hello"]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn into_vec(self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        repeat_clones(self.value, self.len).collect()
+    }
+}
+
+impl<T> Index<usize> for RepeatSlice<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        match self.get(index) {
+            Some(value) => value,
+            None => panic!(
+                "index out of bounds: the len is {} but the index is {}",
+                self.len, index
+            ),
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a RepeatSlice<T> {
+    type Item = &'a T;
+    type IntoIter = Take<Repeat<&'a T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> From<RepeatSlice<T>> for Vec<T>
+where
+    T: Clone,
+{
+    fn from(s: RepeatSlice<T>) -> Self {
+        s.into_vec()
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use crate::tests::Clonable;
+    use crate::RepeatSlice;
+
+    #[test]
+    fn test_repeat_slice() {
+        let count = AtomicU32::new(0);
+        let s = RepeatSlice::new(Clonable { count: &count }, 5);
+        assert_eq!(s.iter().count(), 5);
+        assert!(s.get(5).is_none());
+        assert_eq!(count.load(Ordering::Relaxed), 0);
+        assert_eq!(s.into_vec().len(), 5);
+        assert_eq!(count.load(Ordering::Relaxed), 4);
+    }
+
+    #[test]
+    fn test_repeat_slice_empty() {
+        let count = AtomicU32::new(0);
+        let s = RepeatSlice::new(Clonable { count: &count }, 0);
+        assert!(s.is_empty());
+        assert!(s.into_vec().is_empty());
+        assert_eq!(count.load(Ordering::Relaxed), 0);
+    }
+}
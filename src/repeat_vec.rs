@@ -0,0 +1,240 @@
+use alloc::collections::btree_map::{self, BTreeMap};
+use alloc::vec::Vec;
+use core::iter::FusedIterator;
+use core::ops::{Index, IndexMut, Range};
+
+use crate::Prototype;
+
+/// A vector of copies of a value, that only clones the value for elements
+/// that are mutably accessed.
+///
+/// A `RepeatVec` stores one value and a length. Reading an element returns a
+/// reference to the stored value. Mutable access to an element, using
+/// [`get_mut`](Self::get_mut), [`IndexMut`] or [`iter_mut`](Self::iter_mut),
+/// stores a clone for that element first. Converting into a `Vec` with
+/// [`into_vec`](Self::into_vec) gives the original value to one element that
+/// was never mutably accessed.
+///
+/// Example:
+/// ```rust
+/// use zip_clone::RepeatVec;
+///
+/// let mut v = RepeatVec::new(String::from("Hello"), 1000);
+/// v[500].push_str(", world");
+/// assert_eq!(v[499], "Hello");
+/// assert_eq!(v[500], "Hello, world");
+/// assert_eq!(v.materialized(), 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RepeatVec<T> {
+    value: T,
+    len: usize,
+    // Elements that have been mutably accessed, by index.
+    changed: BTreeMap<usize, T>,
+}
+
+impl<T> RepeatVec<T> {
+    /// Create a vector of `len` copies of a value.
+    pub fn new(value: T, len: usize) -> Self {
+        RepeatVec {
+            value,
+            len,
+            changed: BTreeMap::new(),
+        }
+    }
+
+    /// Return the number of elements.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Return `true` if the vector has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Return the number of elements that hold their own clone of the value.
+    pub fn materialized(&self) -> usize {
+        self.changed.len()
+    }
+
+    /// Return a reference to an element, or `None` if the index is out of
+    /// bounds.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.len {
+            return None;
+        }
+        Some(self.changed.get(&index).unwrap_or(&self.value))
+    }
+
+    /// Return an iterator over references to the elements.
+    pub fn iter(&self) -> RepeatVecIter<'_, T> {
+        RepeatVecIter {
+            vec: self,
+            indices: 0..self.len,
+        }
+    }
+}
+
+impl<T> RepeatVec<T>
+where
+    T: Clone,
+{
+    /// Return a mutable reference to an element, cloning the value for it if
+    /// needed, or `None` if the index is out of bounds.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index >= self.len {
+            return None;
+        }
+        let value = &self.value;
+        Some(self.changed.entry(index).or_insert_with(|| value.clone()))
+    }
+
+    /// Return an iterator over mutable references to the elements, cloning
+    /// the value for every element that does not yet have its own clone.
+    pub fn iter_mut(&mut self) -> btree_map::ValuesMut<'_, usize, T> {
+        for index in 0..self.len {
+            if let btree_map::Entry::Vacant(entry) = self.changed.entry(index) {
+                entry.insert(self.value.clone());
+            }
+        }
+        self.changed.values_mut()
+    }
+
+    /// Convert into a `Vec`, cloning the value for each element that does not
+    /// yet have its own clone, except one that receives the original value.
+    pub fn into_vec(self) -> Vec<T> {
+        let mut unchanged = self.len - self.changed.len();
+        let prototype = Prototype::new(self.value);
+        let mut changed = self.changed.into_iter().peekable();
+        let mut vec = Vec::with_capacity(self.len);
+        for index in 0..self.len {
+            match changed.next_if(|(changed, _)| *changed == index) {
+                Some((_, value)) => vec.push(value),
+                None => {
+                    unchanged -= 1;
+                    if unchanged == 0 {
+                        // Once the original is used, all elements are changed.
+                        vec.push(prototype.into_inner());
+                        vec.extend(changed.map(|(_, value)| value));
+                        break;
+                    }
+                    vec.push(prototype.stamp());
+                }
+            }
+        }
+        vec
+    }
+}
+
+/// An iterator over references to the elements of a [`RepeatVec`].
+///
+/// This struct is created by [`RepeatVec::iter`].
+#[derive(Debug, Clone)]
+pub struct RepeatVecIter<'a, T> {
+    vec: &'a RepeatVec<T>,
+    indices: Range<usize>,
+}
+
+impl<'a, T> Iterator for RepeatVecIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.indices.next()?;
+        Some(self.vec.changed.get(&index).unwrap_or(&self.vec.value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.indices.size_hint()
+    }
+}
+
+impl<T> ExactSizeIterator for RepeatVecIter<'_, T> {}
+
+impl<T> FusedIterator for RepeatVecIter<'_, T> {}
+
+impl<T> Index<usize> for RepeatVec<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        match self.get(index) {
+            Some(value) => value,
+            None => panic!(
+                "index out of bounds: the len is {} but the index is {}",
+                self.len, index
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<usize> for RepeatVec<T>
+where
+    T: Clone,
+{
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        let len = self.len;
+        match self.get_mut(index) {
+            Some(value) => value,
+            None => panic!(
+                "index out of bounds: the len is {} but the index is {}",
+                len, index
+            ),
+        }
+    }
+}
+
+impl<T> From<RepeatVec<T>> for Vec<T>
+where
+    T: Clone,
+{
+    fn from(v: RepeatVec<T>) -> Self {
+        v.into_vec()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use crate::tests::Clonable;
+    use crate::RepeatVec;
+
+    #[test]
+    fn test_repeat_vec_read() {
+        let count = AtomicU32::new(0);
+        let v = RepeatVec::new(Clonable { count: &count }, 5);
+        assert_eq!(v.iter().count(), 5);
+        assert!(v.get(4).is_some());
+        assert!(v.get(5).is_none());
+        assert_eq!(count.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn test_repeat_vec_into_vec() {
+        let mut v = RepeatVec::new(String::from("a"), 5);
+        v[1].push('b');
+        v[4].push('c');
+        assert_eq!(v.materialized(), 2);
+        assert_eq!(v.into_vec(), ["a", "ab", "a", "a", "ac"]);
+    }
+
+    #[test]
+    fn test_repeat_vec_into_vec_clones() {
+        let count = AtomicU32::new(0);
+        let mut v = RepeatVec::new(Clonable { count: &count }, 5);
+        v.get_mut(2);
+        assert_eq!(count.load(Ordering::Relaxed), 1);
+        assert_eq!(v.into_vec().len(), 5);
+        assert_eq!(count.load(Ordering::Relaxed), 4);
+    }
+
+    #[test]
+    fn test_repeat_vec_iter_mut() {
+        let mut v = RepeatVec::new(0, 3);
+        v[0] = 5;
+        for (i, value) in v.iter_mut().enumerate() {
+            *value += i;
+        }
+        assert_eq!(Vec::from(v), [5, 1, 2]);
+    }
+}
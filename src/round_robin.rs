@@ -0,0 +1,120 @@
+use alloc::vec::Vec;
+use core::iter::FusedIterator;
+
+/// Zip an iterator to clones of several values, taken in turn.
+///
+/// Item 0 receives a clone of the first value, item 1 a clone of the second
+/// value, and so on, starting again from the first value after the last. The
+/// length of the iterator determines which item is the last to use each
+/// value, and that item receives the original instead of a clone. Assigning
+/// `k` values to `n` items uses `n - k` clones if `n >= k`, and no clones
+/// otherwise.
+///
+/// Example:
+/// ```rust
+/// use zip_clone::round_robin_clones;
+///
+/// let shards = vec![String::from("a"), String::from("b")];
+/// let assigned = round_robin_clones(0..5, shards).collect::<Vec<_>>();
+/// assert_eq!(assigned[0], (0, String::from("a")));
+/// assert_eq!(assigned[3], (3, String::from("b")));
+/// assert_eq!(assigned[4], (4, String::from("a")));
+/// ```
+///
+/// # Panics
+///
+/// Panics if `values` is empty.
+pub fn round_robin_clones<I, V, C>(iter: I, values: V) -> RoundRobinClones<I::IntoIter, C>
+where
+    I: IntoIterator,
+    I::IntoIter: ExactSizeIterator,
+    V: IntoIterator<Item = C>,
+    C: Clone,
+{
+    let values = values.into_iter().map(Some).collect::<Vec<_>>();
+    assert!(
+        !values.is_empty(),
+        "round_robin_clones needs at least one value"
+    );
+    RoundRobinClones {
+        iter: iter.into_iter(),
+        values,
+        next: 0,
+    }
+}
+
+/// An iterator that pairs items with clones of several values, taken in turn.
+///
+/// This struct is created by [`round_robin_clones`].
+pub struct RoundRobinClones<I, C> {
+    iter: I,
+    values: Vec<Option<C>>,
+    // The index of the value for the next item.
+    next: usize,
+}
+
+impl<I, C> Iterator for RoundRobinClones<I, C>
+where
+    I: ExactSizeIterator,
+    C: Clone,
+{
+    type Item = (I::Item, C);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.iter.next()?;
+        let len = self.values.len();
+        let slot = &mut self.values[self.next];
+        self.next = (self.next + 1) % len;
+        // With fewer remaining items than values, this value is not used again.
+        let value = if self.iter.len() < len {
+            slot.take()?
+        } else {
+            slot.clone()?
+        };
+        Some((item, value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I, C> ExactSizeIterator for RoundRobinClones<I, C>
+where
+    I: ExactSizeIterator,
+    C: Clone,
+{
+}
+
+impl<I, C> FusedIterator for RoundRobinClones<I, C>
+where
+    I: ExactSizeIterator + FusedIterator,
+    C: Clone,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use crate::round_robin_clones;
+    use crate::tests::Clonable;
+
+    #[test]
+    fn test_round_robin_clones() {
+        let count = AtomicU32::new(0);
+        let values = vec![Clonable { count: &count }; 3];
+        count.store(0, Ordering::Relaxed);
+        assert_eq!(round_robin_clones(0..10, values).count(), 10);
+        assert_eq!(count.load(Ordering::Relaxed), 7);
+    }
+
+    #[test]
+    fn test_round_robin_clones_fewer_items() {
+        let count = AtomicU32::new(0);
+        let values = vec![Clonable { count: &count }; 3];
+        count.store(0, Ordering::Relaxed);
+        assert_eq!(round_robin_clones(0..2, values).count(), 2);
+        assert_eq!(count.load(Ordering::Relaxed), 0);
+    }
+}
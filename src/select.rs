@@ -0,0 +1,135 @@
+use alloc::vec::Vec;
+use core::iter::FusedIterator;
+
+/// Zip an iterator to a cloned value, giving the original value to the item
+/// with the largest key.
+///
+/// By default, the item that receives the original value is determined by its
+/// position. Some items benefit more from the original, for example one that
+/// will grow the value the most and so avoid reallocating a clone with exact
+/// capacity. This function buffers all items, selects the item for which
+/// `key` returns the largest value (the last such item if several are equally
+/// large), and gives that item the original value.
+///
+/// The value is cloned one fewer times than the number of items.
+///
+/// Example:
+/// ```rust
+/// use zip_clone::zip_clone_original_by_key;
+///
+/// let mut s = String::with_capacity(20);
+/// s.push_str("Sent to ");
+/// let ptr = s.as_ptr();
+/// let names = ["Al", "Barbara", "Cy"];
+/// for (name, mut s) in zip_clone_original_by_key(names, s, |name| name.len()) {
+///     s.push_str(name);
+///     assert_eq!(s.as_ptr() == ptr, name == "Barbara");
+/// }
+/// ```
+pub fn zip_clone_original_by_key<I, C, K, F>(
+    iter: I,
+    value: C,
+    mut key: F,
+) -> ZipCloneOriginalByKey<I::Item, C>
+where
+    I: IntoIterator,
+    C: Clone,
+    K: Ord,
+    F: FnMut(&I::Item) -> K,
+{
+    let items = iter.into_iter().collect::<Vec<_>>();
+    let original_index = items
+        .iter()
+        .enumerate()
+        .max_by_key(|(_, item)| key(item))
+        .map_or(0, |(index, _)| index);
+    ZipCloneOriginalByKey {
+        items: items.into_iter(),
+        index: 0,
+        original_index,
+        held: Some(value),
+    }
+}
+
+/// An iterator that pairs items with clones of a value, giving the original to
+/// a selected item.
+///
+/// This struct is created by [`zip_clone_original_by_key`].
+pub struct ZipCloneOriginalByKey<T, C> {
+    items: alloc::vec::IntoIter<T>,
+    index: usize,
+    original_index: usize,
+    held: Option<C>,
+}
+
+impl<T, C> Iterator for ZipCloneOriginalByKey<T, C>
+where
+    C: Clone,
+{
+    type Item = (T, C);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.items.next()?;
+        let index = self.index;
+        self.index += 1;
+        if self.items.len() == 0 {
+            // Before the selected item, the held value is the original. After
+            // it, the held value is a clone that is no longer needed.
+            self.held.take().map(|held| (item, held))
+        } else if index == self.original_index {
+            let original = self.held.take()?;
+            self.held = Some(original.clone());
+            Some((item, original))
+        } else {
+            self.held.as_ref().map(|held| (item, held.clone()))
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.items.size_hint()
+    }
+}
+
+impl<T, C> ExactSizeIterator for ZipCloneOriginalByKey<T, C> where C: Clone {}
+
+impl<T, C> FusedIterator for ZipCloneOriginalByKey<T, C> where C: Clone {}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use crate::tests::Clonable;
+    use crate::zip_clone_original_by_key;
+
+    #[test]
+    fn test_original_by_key() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let iter = zip_clone_original_by_key(vec![1, 5, 2, 3], cloned, |&item| item);
+        assert_eq!(iter.map(|(item, _)| item).collect::<Vec<_>>(), [1, 5, 2, 3]);
+        assert_eq!(count.load(Ordering::Relaxed), 3);
+    }
+
+    #[test]
+    fn test_original_by_key_selects_item() {
+        let s = String::from("Hello");
+        let ptr = s.as_ptr();
+        let strings = zip_clone_original_by_key(vec![1, 5, 2, 3], s, |&item| item)
+            .map(|(_, s)| s)
+            .collect::<Vec<_>>();
+        let ptrs = strings
+            .iter()
+            .map(|s| s.as_ptr() == ptr)
+            .collect::<Vec<_>>();
+        assert_eq!(ptrs, [false, true, false, false]);
+    }
+
+    #[test]
+    fn test_original_by_key_empty() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let iter = zip_clone_original_by_key(Vec::<u32>::new(), cloned, |&item| item);
+        assert_eq!(iter.count(), 0);
+        assert_eq!(count.load(Ordering::Relaxed), 0);
+    }
+}
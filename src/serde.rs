@@ -0,0 +1,168 @@
+//! Duplicate values through serialization.
+//!
+//! Some types implement `Serialize` and `Deserialize` but not `Clone`. For
+//! these types, [`zip_dup_serde`] serializes the value once and deserializes a
+//! fresh copy for each item except the last, which receives the original.
+
+use std::iter::{FusedIterator, Peekable};
+
+use ::serde::de::DeserializeOwned;
+use ::serde::Serialize;
+
+/// Zip an iterator to a value duplicated by serialization.
+///
+/// The value is serialized once when this function is called. Each item except
+/// the last is paired with a value deserialized from the serialized form, and
+/// the last item is paired with the original value.
+///
+/// Returns an error if the value cannot be serialized, or cannot be
+/// deserialized from its own serialization. The latter can occur for types
+/// that require a self-describing format, such as those using
+/// `#[serde(untagged)]`, `#[serde(flatten)]` or `skip_serializing_if`. To check
+/// this, one copy is deserialized when this function is called, and is used
+/// for the first item. If the iterator has fewer than two items, this copy is
+/// not needed, so the value is deserialized once more than for `n - 1`
+/// copies.
+///
+/// # Panics
+///
+/// The iterator panics if deserializing a later copy fails although the
+/// check succeeded, which can only happen if the `Deserialize` implementation
+/// of `C` is not deterministic.
+///
+/// Example:
+/// ```rust
+/// use zip_clone::serde::zip_dup_serde;
+///
+/// let v = vec![String::from("Hello")];
+/// for (i, v) in zip_dup_serde(0..10, v).unwrap() {
+///     assert_eq!(v, [String::from("Hello")]);
+/// }
+/// ```
+pub fn zip_dup_serde<I, C>(iter: I, value: C) -> Result<ZipDupSerde<I, C>, bincode::Error>
+where
+    I: Iterator,
+    C: Serialize + DeserializeOwned,
+{
+    let serialized = bincode::serialize(&value)?;
+    let spare = bincode::deserialize(&serialized)?;
+    Ok(ZipDupSerde {
+        iter: iter.peekable(),
+        serialized,
+        spare: Some(spare),
+        value: Some(value),
+    })
+}
+
+/// An iterator that pairs items with values duplicated by serialization.
+///
+/// This struct is created by [`zip_dup_serde`].
+pub struct ZipDupSerde<I, C>
+where
+    I: Iterator,
+{
+    iter: Peekable<I>,
+    serialized: Vec<u8>,
+    // The copy deserialized to check the serialization, until it is used.
+    spare: Option<C>,
+    value: Option<C>,
+}
+
+impl<I, C> Iterator for ZipDupSerde<I, C>
+where
+    I: Iterator,
+    C: DeserializeOwned,
+{
+    type Item = (I::Item, C);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.value.as_ref()?;
+        let item = match self.iter.next() {
+            Some(item) => item,
+            None => {
+                self.value = None;
+                return None;
+            }
+        };
+        if self.iter.peek().is_some() {
+            let duplicate = match self.spare.take() {
+                Some(spare) => spare,
+                // Deserializing the same bytes succeeded when checked.
+                None => bincode::deserialize(&self.serialized)
+                    .expect("value cannot be deserialized from its serialization"),
+            };
+            Some((item, duplicate))
+        } else {
+            self.value.take().map(|value| (item, value))
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.value.is_some() {
+            self.iter.size_hint()
+        } else {
+            (0, Some(0))
+        }
+    }
+}
+
+impl<I, C> ExactSizeIterator for ZipDupSerde<I, C>
+where
+    I: ExactSizeIterator,
+    C: DeserializeOwned,
+{
+}
+
+impl<I, C> FusedIterator for ZipDupSerde<I, C>
+where
+    I: Iterator,
+    C: DeserializeOwned,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::zip_dup_serde;
+
+    #[test]
+    fn test_zip_dup_serde() {
+        let value = vec![String::from("Hello")];
+        let ptr = value.as_ptr();
+        let values = zip_dup_serde(1..6, value)
+            .unwrap()
+            .map(|(_, v)| v)
+            .collect::<Vec<_>>();
+        assert_eq!(values.len(), 5);
+        assert!(values.iter().all(|v| v == &[String::from("Hello")]));
+        // The original value is moved into the last item.
+        assert_eq!(values[4].as_ptr(), ptr);
+        assert_ne!(values[0].as_ptr(), ptr);
+    }
+
+    #[test]
+    fn test_zip_dup_serde_empty() {
+        assert_eq!(zip_dup_serde(0..0, 5u32).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_zip_dup_serde_mismatched() {
+        // A value that serializes as a byte but deserializes from a `u64`.
+        struct Mismatched;
+
+        impl Serialize for Mismatched {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_u8(1)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for Mismatched {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                u64::deserialize(deserializer).map(|_| Mismatched)
+            }
+        }
+
+        assert!(zip_dup_serde(0..2, Mismatched).is_err());
+    }
+}
@@ -0,0 +1,495 @@
+use alloc::rc::Rc;
+use alloc::sync::Arc;
+use core::iter::FusedIterator;
+#[cfg(feature = "std")]
+use core::iter::Peekable;
+use core::ops::Deref;
+#[cfg(feature = "std")]
+use std::time::{Duration, Instant};
+
+use crate::{zip_clone, ZipCloneIter};
+
+/// A reference-counted pointer that can be cheaply shared between items.
+///
+/// This is implemented for [`Rc`] and [`Arc`], and for `triomphe::Arc` when
+/// the `triomphe` feature is enabled. Other reference-counted types can
+/// implement it to be used with [`zip_shared`].
+pub trait SharedPointer: Clone {
+    /// The type of the shared value.
+    type Target;
+
+    /// Create a new pointer owning `value`.
+    fn new(value: Self::Target) -> Self;
+
+    /// Return the value if this is the only pointer to it, or return the
+    /// pointer unchanged.
+    fn try_unwrap(this: Self) -> Result<Self::Target, Self>;
+}
+
+impl<T> SharedPointer for Rc<T> {
+    type Target = T;
+
+    fn new(value: T) -> Self {
+        Rc::new(value)
+    }
+
+    fn try_unwrap(this: Self) -> Result<T, Self> {
+        Rc::try_unwrap(this)
+    }
+}
+
+impl<T> SharedPointer for Arc<T> {
+    type Target = T;
+
+    fn new(value: T) -> Self {
+        Arc::new(value)
+    }
+
+    fn try_unwrap(this: Self) -> Result<T, Self> {
+        Arc::try_unwrap(this)
+    }
+}
+
+#[cfg(feature = "triomphe")]
+impl<T> SharedPointer for triomphe::Arc<T> {
+    type Target = T;
+
+    fn new(value: T) -> Self {
+        triomphe::Arc::new(value)
+    }
+
+    fn try_unwrap(this: Self) -> Result<T, Self> {
+        triomphe::Arc::try_unwrap(this)
+    }
+}
+
+/// Zip an iterator to a shared value.
+///
+/// The value is moved into a single shared pointer of type `P`, and each item
+/// is paired with a pointer to it. The value itself is never cloned. Once all
+/// other pointers are dropped, [`SharedPointer::try_unwrap`] recovers the
+/// value.
+///
+/// Example:
+/// ```rust
+/// use std::rc::Rc;
+/// use zip_clone::{zip_shared, SharedPointer};
+///
+/// let s = String::from("Hello");
+/// let mut pointers = zip_shared::<Rc<_>, _>(0..10, s)
+///     .map(|(_, s)| s)
+///     .collect::<Vec<_>>();
+/// let last = pointers.pop().unwrap();
+/// drop(pointers);
+/// assert_eq!(SharedPointer::try_unwrap(last), Ok(String::from("Hello")));
+/// ```
+pub fn zip_shared<P, I>(iter: I, value: P::Target) -> ZipCloneIter<I, P>
+where
+    P: SharedPointer,
+    I: Iterator,
+{
+    zip_clone(iter, P::new(value))
+}
+
+/// The size in bytes above which [`zip_share_or_clone`] shares a value instead
+/// of cloning it.
+pub const DEFAULT_SHARE_THRESHOLD: usize = 128;
+
+/// A value that is either an owned clone or shared through an [`Arc`].
+#[derive(Debug)]
+pub enum ShareOrClone<C> {
+    Cloned(C),
+    Shared(Arc<C>),
+}
+
+impl<C> ShareOrClone<C>
+where
+    C: Clone,
+{
+    /// Return an owned value, cloning it only if it is still shared.
+    pub fn into_owned(self) -> C {
+        match self {
+            ShareOrClone::Cloned(value) => value,
+            ShareOrClone::Shared(shared) => {
+                Arc::try_unwrap(shared).unwrap_or_else(|s| (*s).clone())
+            }
+        }
+    }
+}
+
+impl<C> Deref for ShareOrClone<C> {
+    type Target = C;
+
+    fn deref(&self) -> &C {
+        match self {
+            ShareOrClone::Cloned(value) => value,
+            ShareOrClone::Shared(shared) => shared,
+        }
+    }
+}
+
+/// Zip an iterator to a value that is cloned if small and shared if large.
+///
+/// Values whose size is at most [`DEFAULT_SHARE_THRESHOLD`] bytes are cloned
+/// as in [`zip_clone`]. Larger values are moved into an [`Arc`] and shared.
+/// Note that the size of a type does not include any heap data it owns.
+///
+/// Example:
+/// ```rust
+/// use zip_clone::{zip_share_or_clone, ShareOrClone};
+///
+/// for (i, v) in zip_share_or_clone(0..10, [0u8; 1024]) {
+///     assert!(matches!(v, ShareOrClone::Shared(_)));
+///     assert_eq!(v.len(), 1024);
+/// }
+/// ```
+pub fn zip_share_or_clone<I, C>(iter: I, value: C) -> ZipShareOrClone<I, C>
+where
+    I: Iterator,
+    C: Clone,
+{
+    zip_share_or_clone_with_threshold(iter, value, DEFAULT_SHARE_THRESHOLD)
+}
+
+/// Zip an iterator to a value that is cloned if its size is at most
+/// `threshold` bytes, and shared otherwise.
+///
+/// Example:
+/// ```rust
+/// use zip_clone::{zip_share_or_clone_with_threshold, ShareOrClone};
+///
+/// for (i, s) in zip_share_or_clone_with_threshold(0..10, String::from("Hello"), 0) {
+///     assert!(matches!(s, ShareOrClone::Shared(_)));
+/// }
+/// ```
+pub fn zip_share_or_clone_with_threshold<I, C>(
+    iter: I,
+    value: C,
+    threshold: usize,
+) -> ZipShareOrClone<I, C>
+where
+    I: Iterator,
+    C: Clone,
+{
+    let inner = if core::mem::size_of::<C>() > threshold {
+        ShareInner::Share(zip_clone(iter, Arc::new(value)))
+    } else {
+        ShareInner::Clone(zip_clone(iter, value))
+    };
+    ZipShareOrClone { inner }
+}
+
+/// An iterator that pairs items with values that are cloned or shared.
+///
+/// This struct is created by [`zip_share_or_clone`] and
+/// [`zip_share_or_clone_with_threshold`].
+pub struct ZipShareOrClone<I, C>
+where
+    I: Iterator,
+{
+    inner: ShareInner<I, C>,
+}
+
+enum ShareInner<I, C>
+where
+    I: Iterator,
+{
+    Clone(ZipCloneIter<I, C>),
+    Share(ZipCloneIter<I, Arc<C>>),
+}
+
+fn cloned<T, C>((item, value): (T, C)) -> (T, ShareOrClone<C>) {
+    (item, ShareOrClone::Cloned(value))
+}
+
+fn shared<T, C>((item, value): (T, Arc<C>)) -> (T, ShareOrClone<C>) {
+    (item, ShareOrClone::Shared(value))
+}
+
+impl<I, C> Iterator for ZipShareOrClone<I, C>
+where
+    I: Iterator,
+    C: Clone,
+{
+    type Item = (I::Item, ShareOrClone<C>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.inner {
+            ShareInner::Clone(iter) => iter.next().map(cloned),
+            ShareInner::Share(iter) => iter.next().map(shared),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match &self.inner {
+            ShareInner::Clone(iter) => iter.size_hint(),
+            ShareInner::Share(iter) => iter.size_hint(),
+        }
+    }
+
+    fn count(self) -> usize
+    where
+        Self: Sized,
+    {
+        match self.inner {
+            ShareInner::Clone(iter) => iter.count(),
+            ShareInner::Share(iter) => iter.count(),
+        }
+    }
+
+    fn last(self) -> Option<Self::Item>
+    where
+        Self: Sized,
+    {
+        match self.inner {
+            ShareInner::Clone(iter) => iter.last().map(cloned),
+            ShareInner::Share(iter) => iter.last().map(shared),
+        }
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        match &mut self.inner {
+            ShareInner::Clone(iter) => iter.nth(n).map(cloned),
+            ShareInner::Share(iter) => iter.nth(n).map(shared),
+        }
+    }
+}
+
+impl<I, C> DoubleEndedIterator for ZipShareOrClone<I, C>
+where
+    I: DoubleEndedIterator,
+    C: Clone,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match &mut self.inner {
+            ShareInner::Clone(iter) => iter.next_back().map(cloned),
+            ShareInner::Share(iter) => iter.next_back().map(shared),
+        }
+    }
+}
+
+impl<I, C> ExactSizeIterator for ZipShareOrClone<I, C>
+where
+    I: ExactSizeIterator,
+    C: Clone,
+{
+}
+
+impl<I, C> FusedIterator for ZipShareOrClone<I, C>
+where
+    I: Iterator,
+    C: Clone,
+{
+}
+
+/// Zip an iterator to a value that is cloned if cloning is fast, and shared
+/// otherwise.
+///
+/// The first clone of the value is timed. If it takes longer than
+/// `max_clone_time`, the value is moved into an [`Arc`] and shared with the
+/// remaining items. Otherwise the value is cloned for the remaining items as
+/// in [`zip_clone`]. This suits generic code that cannot know in advance how
+/// expensive its values are to clone.
+///
+/// Example:
+/// ```rust
+/// use std::time::Duration;
+/// use zip_clone::zip_share_or_clone_adaptive;
+///
+/// let v = vec![0u8; 1 << 20];
+/// for (i, v) in zip_share_or_clone_adaptive(0..10, v, Duration::from_micros(10)) {
+///     assert_eq!(v.len(), 1 << 20);
+/// }
+/// ```
+#[cfg(feature = "std")]
+pub fn zip_share_or_clone_adaptive<I, C>(
+    iter: I,
+    value: C,
+    max_clone_time: Duration,
+) -> ZipShareOrCloneAdaptive<I, C>
+where
+    I: Iterator,
+    C: Clone,
+{
+    ZipShareOrCloneAdaptive {
+        state: AdaptiveState::Measuring(iter.peekable(), value, max_clone_time),
+    }
+}
+
+/// An iterator that pairs items with values that are cloned or shared,
+/// depending on how long the first clone takes.
+///
+/// This struct is created by [`zip_share_or_clone_adaptive`].
+#[cfg(feature = "std")]
+pub struct ZipShareOrCloneAdaptive<I, C>
+where
+    I: Iterator,
+{
+    state: AdaptiveState<I, C>,
+}
+
+#[cfg(feature = "std")]
+enum AdaptiveState<I, C>
+where
+    I: Iterator,
+{
+    // No clone has been made yet.
+    Measuring(Peekable<I>, C, Duration),
+    Decided(ZipShareOrClone<Peekable<I>, C>),
+    Done,
+}
+
+#[cfg(feature = "std")]
+impl<I, C> Iterator for ZipShareOrCloneAdaptive<I, C>
+where
+    I: Iterator,
+    C: Clone,
+{
+    type Item = (I::Item, ShareOrClone<C>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match core::mem::replace(&mut self.state, AdaptiveState::Done) {
+            AdaptiveState::Measuring(mut iter, value, max_clone_time) => {
+                let item = iter.next()?;
+                if iter.peek().is_none() {
+                    return Some((item, ShareOrClone::Cloned(value)));
+                }
+                let start = Instant::now();
+                let clone = value.clone();
+                let inner = if start.elapsed() > max_clone_time {
+                    ShareInner::Share(zip_clone(iter, Arc::new(value)))
+                } else {
+                    ShareInner::Clone(zip_clone(iter, value))
+                };
+                self.state = AdaptiveState::Decided(ZipShareOrClone { inner });
+                Some((item, ShareOrClone::Cloned(clone)))
+            }
+            AdaptiveState::Decided(mut iter) => {
+                let next = iter.next();
+                self.state = AdaptiveState::Decided(iter);
+                next
+            }
+            AdaptiveState::Done => None,
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match &self.state {
+            AdaptiveState::Measuring(iter, _, _) => iter.size_hint(),
+            AdaptiveState::Decided(iter) => iter.size_hint(),
+            AdaptiveState::Done => (0, Some(0)),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<I, C> ExactSizeIterator for ZipShareOrCloneAdaptive<I, C>
+where
+    I: ExactSizeIterator,
+    C: Clone,
+{
+}
+
+#[cfg(feature = "std")]
+impl<I, C> FusedIterator for ZipShareOrCloneAdaptive<I, C>
+where
+    I: Iterator,
+    C: Clone,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
+
+    #[cfg(feature = "std")]
+    use std::time::Duration;
+
+    use crate::tests::Clonable;
+    #[cfg(feature = "std")]
+    use crate::zip_share_or_clone_adaptive;
+    use crate::{zip_share_or_clone_with_threshold, zip_shared, ShareOrClone, SharedPointer};
+
+    #[test]
+    fn test_zip_shared() {
+        let iter = 1..6;
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let pointers = zip_shared::<Rc<_>, _>(iter, cloned)
+            .map(|(_, p)| p)
+            .collect::<Vec<_>>();
+        assert_eq!(pointers.len(), 5);
+        assert_eq!(Rc::strong_count(&pointers[0]), 5);
+        assert_eq!(count.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn test_try_unwrap() {
+        let p = <Arc<_> as SharedPointer>::new(5);
+        let q = p.clone();
+        let p = SharedPointer::try_unwrap(p).unwrap_err();
+        drop(q);
+        assert_eq!(SharedPointer::try_unwrap(p), Ok(5));
+    }
+
+    #[test]
+    fn test_share_or_clone_small() {
+        let iter = 1..6;
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let threshold = std::mem::size_of::<Clonable>();
+        for (_, c) in zip_share_or_clone_with_threshold(iter, cloned, threshold) {
+            assert!(matches!(c, ShareOrClone::Cloned(_)));
+        }
+        assert_eq!(count.load(Ordering::Relaxed), 4);
+    }
+
+    #[test]
+    fn test_share_or_clone_large() {
+        let iter = 1..6;
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let threshold = std::mem::size_of::<Clonable>() - 1;
+        for (_, c) in zip_share_or_clone_with_threshold(iter, cloned, threshold) {
+            assert!(matches!(c, ShareOrClone::Shared(_)));
+        }
+        assert_eq!(count.load(Ordering::Relaxed), 0);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_share_or_clone_adaptive_fast() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let limit = Duration::from_secs(60);
+        for (_, c) in zip_share_or_clone_adaptive(1..6, cloned, limit) {
+            assert!(matches!(c, ShareOrClone::Cloned(_)));
+        }
+        assert_eq!(count.load(Ordering::Relaxed), 4);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_share_or_clone_adaptive_slow() {
+        struct Slow<'a>(Clonable<'a>);
+        impl Clone for Slow<'_> {
+            fn clone(&self) -> Self {
+                std::thread::sleep(Duration::from_millis(2));
+                Slow(self.0.clone())
+            }
+        }
+        let count = AtomicU32::new(0);
+        let cloned = Slow(Clonable { count: &count });
+        let limit = Duration::from_millis(1);
+        let values = zip_share_or_clone_adaptive(1..6, cloned, limit)
+            .map(|(_, c)| c)
+            .collect::<Vec<_>>();
+        assert!(matches!(values[0], ShareOrClone::Cloned(_)));
+        assert!(values[1..]
+            .iter()
+            .all(|c| matches!(c, ShareOrClone::Shared(_))));
+        assert_eq!(count.load(Ordering::Relaxed), 1);
+    }
+}
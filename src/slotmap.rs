@@ -0,0 +1,53 @@
+//! Insert clones of a value into a [`slotmap`](::slotmap) map.
+
+use ::slotmap::{Key, SlotMap};
+
+use crate::ZipClone;
+
+/// Insert `n` copies of a value into a slot map, returning their keys.
+///
+/// The first insertion uses the original value, so `n` insertions make `n - 1`
+/// clones.
+///
+/// Example:
+/// ```rust
+/// use slotmap::{DefaultKey, SlotMap};
+/// use zip_clone::slotmap::insert_clones;
+///
+/// let mut map: SlotMap<DefaultKey, String> = SlotMap::new();
+/// let keys = insert_clones(&mut map, 3, String::from("Hello"));
+/// assert_eq!(keys.len(), 3);
+/// assert!(keys.iter().all(|&key| map[key] == "Hello"));
+/// ```
+pub fn insert_clones<K, V>(map: &mut SlotMap<K, V>, n: usize, value: V) -> Vec<K>
+where
+    K: Key,
+    V: Clone,
+{
+    map.reserve(n);
+    (0..n)
+        .zip_clone(value)
+        .map(|(_, value)| map.insert(value))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use ::slotmap::{DefaultKey, SlotMap};
+
+    use super::insert_clones;
+    use crate::tests::Clonable;
+
+    #[test]
+    fn test_insert_clones() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let mut map: SlotMap<DefaultKey, Clonable> = SlotMap::new();
+        let keys = insert_clones(&mut map, 5, cloned);
+        assert_eq!(keys.len(), 5);
+        assert!(keys.iter().all(|&key| map.contains_key(key)));
+        assert_eq!(count.load(Ordering::Relaxed), 4);
+    }
+}
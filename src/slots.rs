@@ -0,0 +1,150 @@
+use core::mem::MaybeUninit;
+
+/// A destination that can receive one value.
+pub trait Slot<T>: Sized {
+    /// Move a value into the slot.
+    fn put(self, value: T);
+
+    /// Put a clone of a value into the slot.
+    ///
+    /// Implementations may reuse resources of a value already in the slot, for
+    /// example using [`Clone::clone_from`].
+    fn put_clone(self, value: &T)
+    where
+        T: Clone,
+    {
+        self.put(value.clone());
+    }
+}
+
+impl<T> Slot<T> for &mut T {
+    fn put(self, value: T) {
+        *self = value;
+    }
+
+    fn put_clone(self, value: &T)
+    where
+        T: Clone,
+    {
+        self.clone_from(value);
+    }
+}
+
+impl<T> Slot<T> for &mut Option<T> {
+    fn put(self, value: T) {
+        *self = Some(value);
+    }
+
+    fn put_clone(self, value: &T)
+    where
+        T: Clone,
+    {
+        match self {
+            Some(existing) => existing.clone_from(value),
+            None => *self = Some(value.clone()),
+        }
+    }
+}
+
+// Writing to a `MaybeUninit` does not drop any existing value.
+impl<T> Slot<T> for &mut MaybeUninit<T> {
+    fn put(self, value: T) {
+        self.write(value);
+    }
+}
+
+/// A collection of destinations that can each receive one value.
+///
+/// This is implemented for every iterable collection of [`Slot`]s, such as
+/// `&mut [T]`, `&mut [Option<T>]`, `&mut [MaybeUninit<T>]`, or iterators over
+/// mutable references.
+pub trait Slots<T> {
+    type Slot: Slot<T>;
+    type IntoSlots: Iterator<Item = Self::Slot>;
+
+    /// Return an iterator over the slots.
+    fn into_slots(self) -> Self::IntoSlots;
+}
+
+impl<S, T> Slots<T> for S
+where
+    S: IntoIterator,
+    S::Item: Slot<T>,
+{
+    type Slot = S::Item;
+    type IntoSlots = S::IntoIter;
+
+    fn into_slots(self) -> Self::IntoSlots {
+        self.into_iter()
+    }
+}
+
+/// Put a copy of a value into every slot.
+///
+/// Every slot except the last receives a clone of the value, using
+/// [`Slot::put_clone`] to reuse existing resources where possible. The last
+/// slot receives the original value. Returns the number of slots filled.
+///
+/// Example:
+/// ```rust
+/// use zip_clone::fill_from_prototype;
+///
+/// let mut v = vec![String::new(); 3];
+/// assert_eq!(fill_from_prototype(&mut v, String::from("Hello")), 3);
+/// assert_eq!(v, ["Hello", "Hello", "Hello"]);
+/// ```
+pub fn fill_from_prototype<S, T>(slots: S, value: T) -> usize
+where
+    S: Slots<T>,
+    T: Clone,
+{
+    let mut pending = None;
+    let mut filled = 0;
+    for slot in slots.into_slots() {
+        if let Some(previous) = pending.replace(slot) {
+            Slot::put_clone(previous, &value);
+            filled += 1;
+        }
+    }
+    if let Some(slot) = pending {
+        slot.put(value);
+        filled += 1;
+    }
+    filled
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use crate::fill_from_prototype;
+    use crate::tests::Clonable;
+
+    #[test]
+    fn test_fill_slice() {
+        let count = AtomicU32::new(0);
+        let mut v = vec![0; 5];
+        assert_eq!(fill_from_prototype(&mut v, 7), 5);
+        assert_eq!(v, [7; 5]);
+        let mut v = (0..5).map(|_| None).collect::<Vec<Option<Clonable>>>();
+        assert_eq!(fill_from_prototype(&mut v, Clonable { count: &count }), 5);
+        assert_eq!(count.load(Ordering::Relaxed), 4);
+    }
+
+    #[test]
+    fn test_fill_reuses_allocation() {
+        let mut v = vec![String::with_capacity(10), String::new()];
+        let ptr = v[0].as_ptr();
+        fill_from_prototype(&mut v, String::from("Hello"));
+        assert_eq!(v, ["Hello", "Hello"]);
+        assert_eq!(v[0].as_ptr(), ptr);
+    }
+
+    #[test]
+    fn test_fill_empty() {
+        let count = AtomicU32::new(0);
+        let mut v: Vec<Clonable> = Vec::new();
+        assert_eq!(fill_from_prototype(&mut v, Clonable { count: &count }), 0);
+        assert_eq!(count.load(Ordering::Relaxed), 0);
+    }
+}
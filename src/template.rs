@@ -0,0 +1,155 @@
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use core::iter::{FusedIterator, Peekable};
+
+/// A string template containing a single `{}` placeholder.
+///
+/// The pattern is split once, when the template is created. Each rendered
+/// string is allocated with exactly the capacity it needs.
+///
+/// Example:
+/// ```rust
+/// use zip_clone::TemplateString;
+///
+/// let template = TemplateString::new("Sent to {}.");
+/// assert_eq!(template.render("Alice"), "Sent to Alice.");
+/// let rendered = template.render_each(["Bob", "Carol"]).collect::<Vec<_>>();
+/// assert_eq!(rendered, ["Sent to Bob.", "Sent to Carol."]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct TemplateString {
+    prefix: String,
+    suffix: String,
+}
+
+impl TemplateString {
+    /// Create a template from a pattern.
+    ///
+    /// The placeholder is the first `{}` in the pattern. If the pattern does
+    /// not contain `{}`, items are appended to the end of the pattern.
+    pub fn new(pattern: &str) -> Self {
+        let (prefix, suffix) = match pattern.find("{}") {
+            Some(index) => (&pattern[..index], &pattern[index + 2..]),
+            None => (pattern, ""),
+        };
+        TemplateString {
+            prefix: prefix.to_owned(),
+            suffix: suffix.to_owned(),
+        }
+    }
+
+    /// Render the template with an item in place of the placeholder.
+    pub fn render(&self, item: &str) -> String {
+        let mut rendered = String::with_capacity(self.rendered_len(item));
+        rendered.push_str(&self.prefix);
+        rendered.push_str(item);
+        rendered.push_str(&self.suffix);
+        rendered
+    }
+
+    /// Render the template for each item.
+    ///
+    /// Each string except the last is allocated with its exact capacity. The
+    /// last string reuses the template's own buffer, growing it to its exact
+    /// capacity.
+    pub fn render_each<I>(self, iter: I) -> RenderEach<I::IntoIter>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        RenderEach {
+            iter: iter.into_iter().peekable(),
+            template: Some(self),
+        }
+    }
+
+    fn rendered_len(&self, item: &str) -> usize {
+        self.prefix.len() + item.len() + self.suffix.len()
+    }
+}
+
+/// An iterator that renders a template for each item.
+///
+/// This struct is created by [`TemplateString::render_each`].
+pub struct RenderEach<I>
+where
+    I: Iterator,
+{
+    iter: Peekable<I>,
+    template: Option<TemplateString>,
+}
+
+impl<I> Iterator for RenderEach<I>
+where
+    I: Iterator,
+    I::Item: AsRef<str>,
+{
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        let template = self.template.as_ref()?;
+        let item = match self.iter.next() {
+            Some(item) => item,
+            None => {
+                self.template = None;
+                return None;
+            }
+        };
+        let item = item.as_ref();
+        if self.iter.peek().is_some() {
+            return Some(template.render(item));
+        }
+        self.template.take().map(|template| {
+            let mut rendered = template.prefix;
+            rendered.reserve_exact(item.len() + template.suffix.len());
+            rendered.push_str(item);
+            rendered.push_str(&template.suffix);
+            rendered
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.template.is_some() {
+            self.iter.size_hint()
+        } else {
+            (0, Some(0))
+        }
+    }
+}
+
+impl<I> ExactSizeIterator for RenderEach<I>
+where
+    I: ExactSizeIterator,
+    I::Item: AsRef<str>,
+{
+}
+
+impl<I> FusedIterator for RenderEach<I>
+where
+    I: Iterator,
+    I::Item: AsRef<str>,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::TemplateString;
+
+    #[test]
+    fn test_render_placeholder_position() {
+        assert_eq!(TemplateString::new("{} sent").render("Al"), "Al sent");
+        assert_eq!(TemplateString::new("to {}").render("Al"), "to Al");
+        assert_eq!(TemplateString::new("to ").render("Al"), "to Al");
+        assert_eq!(TemplateString::new("{}{}").render("Al"), "Al{}");
+    }
+
+    #[test]
+    fn test_render_each_exact_capacity() {
+        let template = TemplateString::new("Sent to {}.");
+        let rendered = template
+            .render_each(vec!["Al", "Barbara"])
+            .collect::<Vec<_>>();
+        assert_eq!(rendered, ["Sent to Al.", "Sent to Barbara."]);
+        assert!(rendered.iter().all(|s| s.capacity() == s.len()));
+    }
+}
@@ -0,0 +1,523 @@
+//! Helpers for testing adapters built on this crate.
+//!
+//! This module is available with the `testing` feature.
+
+use std::ops::{Deref, DerefMut, Range};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// A value that counts how many times it has been cloned.
+///
+/// The count is shared between a value and all of its clones.
+///
+/// Example:
+/// ```rust
+/// use zip_clone::testing::Counted;
+/// use zip_clone::ZipClone;
+///
+/// let value = Counted::new(String::from("Hello"));
+/// let counter = value.clone_counter();
+/// assert_eq!((0..10).zip_clone(value).map(|_| ()).count(), 10);
+/// assert_eq!(counter.clones(), 9);
+/// ```
+#[derive(Debug)]
+pub struct Counted<T> {
+    value: T,
+    counter: CloneCounter,
+}
+
+impl<T> Counted<T> {
+    /// Wrap a value to count its clones.
+    pub fn new(value: T) -> Self {
+        Counted {
+            value,
+            counter: CloneCounter::default(),
+        }
+    }
+
+    /// Return a handle to the clone count shared by this value and its clones.
+    pub fn clone_counter(&self) -> CloneCounter {
+        self.counter.clone()
+    }
+
+    /// Return the wrapped value.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T> Clone for Counted<T>
+where
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        self.counter.clones.fetch_add(1, Ordering::Relaxed);
+        Counted {
+            value: self.value.clone(),
+            counter: self.counter.clone(),
+        }
+    }
+}
+
+impl<T> Deref for Counted<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> DerefMut for Counted<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+/// A shared count of clones made of a [`Counted`] value.
+#[derive(Clone, Debug, Default)]
+pub struct CloneCounter {
+    clones: Arc<AtomicUsize>,
+}
+
+impl CloneCounter {
+    /// Return the number of clones made so far.
+    pub fn clones(&self) -> usize {
+        self.clones.load(Ordering::Relaxed)
+    }
+
+    /// Reset the number of clones to zero.
+    pub fn reset(&self) {
+        self.clones.store(0, Ordering::Relaxed);
+    }
+}
+
+/// A value that panics if it is cloned.
+///
+/// Use this to check that a path performs no clones, such as `last`, or
+/// iteration over a single item. [`TryClone::try_clone`](crate::TryClone)
+/// returns an error instead of panicking, to check fallible paths.
+///
+/// Example:
+/// ```rust
+/// use zip_clone::testing::NeverClone;
+/// use zip_clone::ZipClone;
+///
+/// // A single item receives the original value.
+/// let value = NeverClone::new(String::from("Hello"));
+/// let (_, value) = (0..1).zip_clone(value).next().unwrap();
+/// assert_eq!(*value, "Hello");
+/// ```
+#[derive(Debug)]
+pub struct NeverClone<T> {
+    value: T,
+}
+
+impl<T> NeverClone<T> {
+    /// Wrap a value that must not be cloned.
+    pub fn new(value: T) -> Self {
+        NeverClone { value }
+    }
+
+    /// Return the wrapped value.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T> Clone for NeverClone<T> {
+    #[track_caller]
+    fn clone(&self) -> Self {
+        panic!("a NeverClone value was cloned");
+    }
+}
+
+impl<T> crate::TryClone for NeverClone<T> {
+    fn try_clone(&self) -> Result<Self, crate::TryReserveError> {
+        // Requesting more than `isize::MAX` bytes always fails.
+        Err(Vec::<u8>::new().try_reserve(usize::MAX).unwrap_err())
+    }
+}
+
+impl<T> Deref for NeverClone<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> DerefMut for NeverClone<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+const VALUE: u32 = 7;
+const LENGTHS: Range<u32> = 0..6;
+
+fn check_items<J>(mut iter: J, expected: impl Iterator<Item = u32>, context: &str)
+where
+    J: Iterator<Item = (u32, Counted<u32>)>,
+{
+    for expected in expected {
+        match iter.next() {
+            Some((item, value)) => {
+                assert_eq!(item, expected, "{}: wrong item", context);
+                assert_eq!(*value, VALUE, "{}: wrong value", context);
+            }
+            None => panic!("{}: missing item {}", context, expected),
+        }
+    }
+    assert!(iter.next().is_none(), "{}: too many items", context);
+}
+
+/// Check that an adapter follows the invariants of
+/// [`ZipCloneIter`](crate::ZipCloneIter).
+///
+/// `constructor` creates the adapter from an iterator and a value. The checks
+/// cover the items and values returned, and the number of clones made, for
+/// full iteration and for `last`, `nth` and `find`.
+///
+/// With the `predictable` feature, `last` and `nth` may clone for each item
+/// they pass, as a loop over `next` does, since `ZipCloneIter` makes the same
+/// clones.
+///
+/// This is usually called through [`assert_zip_clone_conformance!`].
+pub fn check_conformance<F, J>(mut constructor: F)
+where
+    F: FnMut(Range<u32>, Counted<u32>) -> J,
+    J: Iterator<Item = (u32, Counted<u32>)>,
+{
+    let predictable = cfg!(feature = "predictable");
+    for n in LENGTHS {
+        let value = Counted::new(VALUE);
+        let counter = value.clone_counter();
+        let context = format!("next with {} items", n);
+        check_items(constructor(0..n, value), 0..n, &context);
+        let expected = n.saturating_sub(1) as usize;
+        assert_eq!(counter.clones(), expected, "{}: clones", context);
+
+        let value = Counted::new(VALUE);
+        let counter = value.clone_counter();
+        let last = constructor(0..n, value).last();
+        let context = format!("last with {} items", n);
+        assert_eq!(last.map(|(item, _)| item), n.checked_sub(1), "{}", context);
+        let max = if predictable {
+            n.saturating_sub(1) as usize
+        } else {
+            0
+        };
+        assert!(counter.clones() <= max, "{}: clones", context);
+
+        for k in 0..=n {
+            let value = Counted::new(VALUE);
+            let counter = value.clone_counter();
+            let nth = constructor(0..n, value).nth(k as usize);
+            let context = format!("nth({}) with {} items", k, n);
+            assert_eq!(
+                nth.map(|(item, _)| item),
+                Some(k).filter(|&k| k < n),
+                "{}",
+                context
+            );
+            let max = if predictable {
+                (k + 1).min(n.saturating_sub(1)) as usize
+            } else {
+                1
+            };
+            assert!(
+                counter.clones() <= max,
+                "{}: {} clones",
+                context,
+                counter.clones()
+            );
+
+            let value = Counted::new(VALUE);
+            let counter = value.clone_counter();
+            let found = constructor(0..n, value).find(|(item, _)| *item == k);
+            let context = format!("find({}) with {} items", k, n);
+            assert_eq!(
+                found.map(|(item, _)| item),
+                Some(k).filter(|&k| k < n),
+                "{}",
+                context
+            );
+            let max = (k + 1).min(n.saturating_sub(1)) as usize;
+            assert!(
+                counter.clones() <= max,
+                "{}: {} clones",
+                context,
+                counter.clones()
+            );
+        }
+
+        let value = Counted::new(VALUE);
+        let counter = value.clone_counter();
+        let mut iter = constructor(0..n, value);
+        let taken = iter.next().is_some() as usize;
+        drop(iter);
+        let context = format!("drop after one of {} items", n);
+        assert!(
+            counter.clones() <= taken,
+            "{}: {} clones",
+            context,
+            counter.clones()
+        );
+    }
+}
+
+/// Check that a double-ended adapter follows the invariants of
+/// [`ZipCloneIter`](crate::ZipCloneIter).
+///
+/// In addition to the checks made by [`check_conformance`], this checks
+/// iteration from the back.
+pub fn check_conformance_double_ended<F, J>(mut constructor: F)
+where
+    F: FnMut(Range<u32>, Counted<u32>) -> J,
+    J: DoubleEndedIterator<Item = (u32, Counted<u32>)>,
+{
+    check_conformance(&mut constructor);
+    for n in LENGTHS {
+        let value = Counted::new(VALUE);
+        let counter = value.clone_counter();
+        let context = format!("next_back with {} items", n);
+        check_items(constructor(0..n, value).rev(), (0..n).rev(), &context);
+        let expected = n.saturating_sub(1) as usize;
+        assert_eq!(counter.clones(), expected, "{}: clones", context);
+    }
+}
+
+/// Assert that an adapter follows the invariants of
+/// [`ZipCloneIter`](crate::ZipCloneIter).
+///
+/// The argument is a function or closure that creates the adapter from a
+/// `Range<u32>` and a [`Counted<u32>`] value. Add `double_ended` to also check
+/// reverse iteration.
+///
+/// Example:
+/// ```rust
+/// use zip_clone::{assert_zip_clone_conformance, zip_clone};
+///
+/// assert_zip_clone_conformance!(zip_clone);
+/// assert_zip_clone_conformance!(|iter, value| zip_clone(iter, value), double_ended);
+/// ```
+#[macro_export]
+macro_rules! assert_zip_clone_conformance {
+    ($constructor:expr) => {
+        $crate::testing::check_conformance($constructor)
+    };
+    ($constructor:expr, double_ended) => {
+        $crate::testing::check_conformance_double_ended($constructor)
+    };
+}
+
+/// An operation applied to an iterator by [`check_steps`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    /// Call `next`.
+    Next,
+    /// Call `next_back`.
+    NextBack,
+    /// Call `nth` with the given index.
+    Nth(usize),
+    /// Call `nth_back` with the given index.
+    NthBack(usize),
+    /// Call `find` for the given item.
+    Find(u32),
+    /// Consume the rest of the iterator with `fold`.
+    Fold,
+    /// Drop the iterator.
+    Drop,
+}
+
+/// Apply a sequence of operations to a [`zip_clone`](crate::zip_clone)
+/// iterator over `0..len`, and check it against a reference implementation.
+///
+/// The reference pairs every item with a new copy of the value, like
+/// `(0..len).zip(repeat_with(..))`, but double-ended. After each operation,
+/// the items returned must be the same as the reference returns, and every
+/// value must equal the original. At the end, at most `len - 1` clones must
+/// have been made. Operations after `Fold` or `Drop` are ignored.
+///
+/// This is the check run by the fuzz target in the repository's `fuzz`
+/// directory.
+///
+/// Example:
+/// ```rust
+/// use zip_clone::testing::{check_steps, Step};
+///
+/// check_steps(10, &[Step::Next, Step::NthBack(2), Step::Find(5), Step::Fold]);
+/// ```
+pub fn check_steps(len: u32, steps: &[Step]) {
+    fn item(pair: Option<(u32, Counted<u32>)>, context: &str) -> Option<u32> {
+        pair.map(|(item, value)| {
+            assert_eq!(*value, VALUE, "{}: wrong value", context);
+            item
+        })
+    }
+
+    let value = Counted::new(VALUE);
+    let counter = value.clone_counter();
+    let mut iter = crate::zip_clone(0..len, value);
+    let mut reference = (0..len).map(|item| (item, VALUE));
+    for (i, step) in steps.iter().enumerate() {
+        let context = format!("step {} ({:?}) with {} items", i, step, len);
+        let (actual, expected) = match *step {
+            Step::Next => (item(iter.next(), &context), reference.next()),
+            Step::NextBack => (item(iter.next_back(), &context), reference.next_back()),
+            Step::Nth(n) => (item(iter.nth(n), &context), reference.nth(n)),
+            Step::NthBack(n) => (item(iter.nth_back(n), &context), reference.nth_back(n)),
+            Step::Find(target) => (
+                item(iter.find(|(item, _)| *item == target), &context),
+                reference.find(|(item, _)| *item == target),
+            ),
+            Step::Fold => {
+                let actual = iter.fold(Vec::new(), |mut items, (item, value)| {
+                    assert_eq!(*value, VALUE, "{}: wrong value", context);
+                    items.push(item);
+                    items
+                });
+                let expected = reference.map(|(item, _)| item).collect::<Vec<_>>();
+                assert_eq!(actual, expected, "{}: wrong items", context);
+                break;
+            }
+            Step::Drop => break,
+        };
+        assert_eq!(
+            actual,
+            expected.map(|(item, _)| item),
+            "{}: wrong item",
+            context
+        );
+    }
+    let max = len.saturating_sub(1) as usize;
+    assert!(
+        counter.clones() <= max,
+        "{} clones for {} items",
+        counter.clones(),
+        len
+    );
+}
+
+/// Run a function with a counted value, and panic if it clones the value more
+/// than `max` times.
+///
+/// The function receives `prototype` wrapped in a [`Counted`]. Returns the
+/// result of the function.
+///
+/// This is usually called through [`assert_max_clones!`].
+#[track_caller]
+pub fn check_max_clones<T, F, R>(max: usize, prototype: T, f: F) -> R
+where
+    F: FnOnce(Counted<T>) -> R,
+{
+    let value = Counted::new(prototype);
+    let counter = value.clone_counter();
+    let result = f(value);
+    assert!(
+        counter.clones() <= max,
+        "made {} clones, more than the maximum of {}",
+        counter.clones(),
+        max
+    );
+    result
+}
+
+/// Assert that a block makes at most a number of clones of a value.
+///
+/// `assert_max_clones!(max, name = prototype, { block })` wraps the prototype
+/// in a [`Counted`], binds it to `name` for the block, and panics if the block
+/// clones it more than `max` times. The macro evaluates to the value of the
+/// block.
+///
+/// Example:
+/// ```rust
+/// use zip_clone::{assert_max_clones, ZipClone};
+///
+/// let lengths = assert_max_clones!(2, value = String::from("Hello"), {
+///     (0..3).zip_clone(value).map(|(_, s)| s.len()).collect::<Vec<_>>()
+/// });
+/// assert_eq!(lengths, [5, 5, 5]);
+/// ```
+#[macro_export]
+macro_rules! assert_max_clones {
+    ($max:expr, $value:ident = $prototype:expr, $block:block) => {
+        $crate::testing::check_max_clones($max, $prototype, |$value| $block)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{zip_clone, zip_clone_batched, ZipClone};
+
+    #[test]
+    fn test_zip_clone_conformance() {
+        assert_zip_clone_conformance!(zip_clone, double_ended);
+    }
+
+    #[test]
+    fn test_zip_clone_batched_conformance() {
+        // Larger batches may clone ahead of use, so only a batch size of 1
+        // makes the same clones as `zip_clone`.
+        assert_zip_clone_conformance!(|iter, value| zip_clone_batched(iter, value, 1));
+    }
+
+    // With the `predictable` feature, a `last` that clones is allowed.
+    #[cfg(not(feature = "predictable"))]
+    #[test]
+    #[should_panic(expected = "last with 2 items: clones")]
+    fn test_nonconformance() {
+        use super::Counted;
+
+        // A wrapper that does not forward `last` makes unnecessary clones.
+        struct Wrapper<I>(I);
+        impl<I: Iterator> Iterator for Wrapper<I> {
+            type Item = I::Item;
+            fn next(&mut self) -> Option<Self::Item> {
+                self.0.next()
+            }
+        }
+        assert_zip_clone_conformance!(|iter: std::ops::Range<u32>, value: Counted<u32>| {
+            Wrapper(iter.zip_clone(value))
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "made 5 clones, more than the maximum of 4")]
+    fn test_assert_max_clones() {
+        // `repeat` clones the value for every item, one more than `zip_clone`.
+        assert_max_clones!(4, value = 1, {
+            (0..5).zip(std::iter::repeat(value)).count()
+        });
+    }
+
+    #[test]
+    fn test_check_steps() {
+        use super::{check_steps, Step};
+
+        for len in 0..6 {
+            check_steps(len, &[Step::Next, Step::NextBack, Step::Fold]);
+            check_steps(len, &[Step::Nth(1), Step::NthBack(1), Step::Next]);
+            check_steps(len, &[Step::Find(2), Step::Find(1), Step::NextBack]);
+            check_steps(len, &[Step::Next, Step::Drop, Step::Next]);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "a NeverClone value was cloned")]
+    fn test_never_clone() {
+        use super::NeverClone;
+
+        (0..2).zip_clone(NeverClone::new(1)).for_each(drop);
+    }
+
+    #[test]
+    fn test_never_clone_try_clone() {
+        use super::NeverClone;
+        use crate::try_zip_clone;
+
+        let mut iter = try_zip_clone(0..2, NeverClone::new(1));
+        assert!(iter.next().unwrap().1.is_err());
+        assert!(iter.next().unwrap().1.is_ok());
+    }
+}
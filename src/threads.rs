@@ -0,0 +1,246 @@
+use std::sync::{Mutex, PoisonError};
+
+use crate::{zip_duplicate, Duplicate, ZipClone, ZipCloneIter};
+
+/// Process a slice in parallel chunks, giving each worker thread its own value.
+///
+/// The slice is split into at most `num_workers` chunks of similar size. Each
+/// chunk is processed on a scoped thread that owns one clone of `value`, with
+/// the original value moved to the thread processing the last chunk. The
+/// closure is called for each item with a mutable reference to the worker's
+/// value.
+///
+/// Example:
+/// ```rust
+/// use zip_clone::par_chunks_for_each_clone;
+///
+/// let mut items = vec![0; 100];
+/// par_chunks_for_each_clone(&mut items, 4, String::from("Hello"), |item, s| {
+///     *item = s.len();
+/// });
+/// assert!(items.iter().all(|&item| item == 5));
+/// ```
+pub fn par_chunks_for_each_clone<T, C, F>(items: &mut [T], num_workers: usize, value: C, f: F)
+where
+    T: Send,
+    C: Clone + Send,
+    F: Fn(&mut T, &mut C) + Sync,
+{
+    if items.is_empty() {
+        return;
+    }
+    let num_workers = num_workers.max(1);
+    let chunk_size = (items.len() + num_workers - 1) / num_workers;
+    let f = &f;
+    std::thread::scope(|scope| {
+        // `zip_clone` gives the original value to the first item, so take the
+        // chunks in reverse to move it to the thread processing the last chunk.
+        for (chunk, mut value) in items.chunks_mut(chunk_size).rev().zip_clone(value) {
+            scope.spawn(move || {
+                for item in chunk {
+                    f(item, &mut value);
+                }
+            });
+        }
+    });
+}
+
+/// A queue of items paired with clones of a value, that several threads can
+/// take pairs from at once.
+///
+/// Each call to [`pop`](Self::pop) returns the next item with a clone of the
+/// value, except for the last item, which receives the original, so exactly
+/// one thread receives the original. The iterator is protected by a mutex, and
+/// the clones are made while holding it.
+///
+/// `&SharedZipClone` implements `Iterator`, so each worker can loop over a
+/// shared reference.
+///
+/// Example:
+/// ```rust
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use zip_clone::SharedZipClone;
+///
+/// let queue = SharedZipClone::new(0..100, String::from("Hello"));
+/// let total = AtomicUsize::new(0);
+/// std::thread::scope(|scope| {
+///     for _ in 0..4 {
+///         scope.spawn(|| {
+///             for (_, s) in &queue {
+///                 total.fetch_add(s.len(), Ordering::Relaxed);
+///             }
+///         });
+///     }
+/// });
+/// assert_eq!(total.into_inner(), 500);
+/// ```
+pub struct SharedZipClone<I, C>
+where
+    I: Iterator,
+{
+    inner: Mutex<ZipCloneIter<I, C, KeepOriginal>>,
+}
+
+// Give each item except the last a clone, keeping the original for the last
+// item. `ByClone` gives the original to the first item instead.
+struct KeepOriginal;
+
+impl<C> Duplicate<C> for KeepOriginal
+where
+    C: Clone,
+{
+    fn duplicate(&mut self, value: &mut C) -> C {
+        value.clone()
+    }
+}
+
+impl<I, C> SharedZipClone<I, C>
+where
+    I: Iterator,
+    C: Clone,
+{
+    /// Create a queue of the items of an iterator, paired with clones of a
+    /// value.
+    pub fn new<J>(iter: J, value: C) -> Self
+    where
+        J: IntoIterator<IntoIter = I>,
+    {
+        SharedZipClone {
+            inner: Mutex::new(zip_duplicate(iter.into_iter(), value, KeepOriginal)),
+        }
+    }
+
+    /// Take the next item and its value, or return `None` if no items remain.
+    ///
+    /// A panic in another thread while it held the lock, such as a panic in
+    /// `Clone`, does not prevent other threads taking the remaining items.
+    /// The value is cloned before it is given away, so it survives a panic in
+    /// `Clone`, but the item that was being taken is lost.
+    pub fn pop(&self) -> Option<(I::Item, C)> {
+        self.inner
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .next()
+    }
+
+    /// Return the value if it was not given to any item.
+    ///
+    /// See [`ZipCloneIter::into_leftover`].
+    pub fn into_leftover(self) -> Option<C> {
+        self.inner
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner)
+            .into_leftover()
+    }
+}
+
+impl<I, C> Iterator for &SharedZipClone<I, C>
+where
+    I: Iterator,
+    C: Clone,
+{
+    type Item = (I::Item, C);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.pop()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use crate::tests::Clonable;
+    use crate::{par_chunks_for_each_clone, SharedZipClone};
+
+    #[test]
+    fn test_par_chunks_for_each_clone() {
+        let mut items = vec![0u32; 100];
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        par_chunks_for_each_clone(&mut items, 4, cloned, |item, _| *item += 1);
+        assert!(items.iter().all(|&item| item == 1));
+        assert_eq!(count.load(Ordering::Relaxed), 3);
+    }
+
+    #[test]
+    fn test_par_chunks_original_last() {
+        let mut items = vec![false; 100];
+        let value = String::from("Hello");
+        let ptr = value.as_ptr() as usize;
+        par_chunks_for_each_clone(&mut items, 4, value, |item, s| {
+            *item = s.as_ptr() as usize == ptr;
+        });
+        assert!(items[..75].iter().all(|&original| !original));
+        assert!(items[75..].iter().all(|&original| original));
+    }
+
+    #[test]
+    fn test_par_chunks_fewer_items_than_workers() {
+        let mut items = vec![0u32; 2];
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        par_chunks_for_each_clone(&mut items, 8, cloned, |item, _| *item += 1);
+        assert_eq!(items, [1, 1]);
+        assert_eq!(count.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn test_shared_zip_clone() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let queue = SharedZipClone::new(0..100, cloned);
+        let items = AtomicU32::new(0);
+        std::thread::scope(|scope| {
+            for _ in 0..4 {
+                scope.spawn(|| {
+                    while let Some((item, _)) = queue.pop() {
+                        items.fetch_add(item, Ordering::Relaxed);
+                    }
+                });
+            }
+        });
+        assert_eq!(items.load(Ordering::Relaxed), 4950);
+        assert_eq!(count.load(Ordering::Relaxed), 99);
+        assert!(queue.into_leftover().is_none());
+    }
+
+    #[test]
+    fn test_shared_zip_clone_original_last() {
+        let value = String::from("Hello");
+        let ptr = value.as_ptr();
+        let queue = SharedZipClone::new(0..3, value);
+        assert_ne!(queue.pop().unwrap().1.as_ptr(), ptr);
+        assert_ne!(queue.pop().unwrap().1.as_ptr(), ptr);
+        assert_eq!(queue.pop().unwrap().1.as_ptr(), ptr);
+        assert!(queue.pop().is_none());
+    }
+
+    #[test]
+    fn test_shared_zip_clone_panic() {
+        // A value whose second clone panics.
+        struct Fragile<'a>(&'a AtomicU32);
+        impl Clone for Fragile<'_> {
+            fn clone(&self) -> Self {
+                if self.0.fetch_add(1, Ordering::Relaxed) == 1 {
+                    panic!("clone failed");
+                }
+                Fragile(self.0)
+            }
+        }
+
+        let count = AtomicU32::new(0);
+        let queue = SharedZipClone::new(0..5, Fragile(&count));
+        assert_eq!(queue.pop().map(|(item, _)| item), Some(0));
+        std::thread::scope(|scope| {
+            assert!(scope.spawn(|| queue.pop()).join().is_err());
+        });
+        let rest = std::thread::scope(|scope| {
+            scope
+                .spawn(|| (&queue).map(|(item, _)| item).collect::<Vec<_>>())
+                .join()
+                .unwrap()
+        });
+        assert_eq!(rest, [2, 3, 4]);
+    }
+}
@@ -0,0 +1,252 @@
+//! Write to [`tokio`](::tokio) sinks with clones of a buffer, and zip streams
+//! to values that can be updated through a `watch` channel.
+
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use ::futures::future::join_all;
+use ::futures::ready;
+use ::futures::stream::{FusedStream, Peekable, Stream, StreamExt};
+use ::tokio::io::{AsyncWrite, AsyncWriteExt};
+use ::tokio::sync::watch;
+
+use crate::ZipClone;
+
+/// Write the same payload to each of several sinks concurrently, then flush
+/// them.
+///
+/// Each write owns a copy of the payload, so sinks can be written at
+/// different rates. The payload is cloned once for each sink except the last,
+/// which receives the original.
+///
+/// Returns the result of writing to each sink, in the same order as the
+/// sinks. An error for one sink does not stop writes to the other sinks.
+///
+/// Example:
+/// ```rust
+/// use futures::executor::block_on;
+/// use zip_clone::tokio::write_all_to_each;
+///
+/// let mut sinks = vec![Vec::new(), Vec::new()];
+/// let results = block_on(write_all_to_each(&mut sinks, b"Hello".to_vec()));
+/// assert!(results.iter().all(|result| result.is_ok()));
+/// assert_eq!(sinks, [b"Hello", b"Hello"]);
+/// ```
+pub async fn write_all_to_each<W, B>(sinks: &mut [W], payload: B) -> Vec<io::Result<()>>
+where
+    W: AsyncWrite + Unpin,
+    B: AsRef<[u8]> + Clone,
+{
+    let writes = sinks
+        .iter_mut()
+        .zip_clone(payload)
+        .map(|(sink, payload)| async move {
+            sink.write_all(payload.as_ref()).await?;
+            sink.flush().await
+        });
+    join_all(writes).await
+}
+
+/// Zip a stream to clones of a value that another task can replace.
+///
+/// The adapter keeps its own copy of the latest value in the `watch` channel,
+/// taken when the adapter is created. Before each item is returned, the copy
+/// is replaced with a clone of the value in the channel if it has changed, so
+/// an update applies to every item returned after it is sent. Each item
+/// receives a clone of the copy, except the last, which receives the copy
+/// itself. If the sender is dropped, the adapter keeps using its copy.
+///
+/// As for [`zip_clone_stream`](crate::futures::zip_clone_stream), the stream
+/// waits for the following item, or the end of the stream, before returning
+/// each item.
+///
+/// Example:
+/// ```rust
+/// use futures::executor::block_on;
+/// use futures::stream::{self, StreamExt};
+/// use tokio::sync::watch;
+/// use zip_clone::tokio::zip_watch_stream;
+///
+/// let (sender, receiver) = watch::channel(String::from("v1"));
+/// let mut stream = zip_watch_stream(stream::iter(0..3), receiver);
+/// assert_eq!(block_on(stream.next()), Some((0, String::from("v1"))));
+/// sender.send(String::from("v2")).unwrap();
+/// assert_eq!(block_on(stream.next()), Some((1, String::from("v2"))));
+/// assert_eq!(block_on(stream.next()), Some((2, String::from("v2"))));
+/// ```
+pub fn zip_watch_stream<S, C>(stream: S, mut receiver: watch::Receiver<C>) -> ZipWatchStream<S, C>
+where
+    S: Stream + Unpin,
+    C: Clone + Unpin,
+{
+    let value = receiver.borrow_and_update().clone();
+    ZipWatchStream {
+        stream: stream.peekable(),
+        pending: None,
+        receiver,
+        value: Some(value),
+    }
+}
+
+/// A stream that pairs items with clones of a value from a `watch` channel.
+///
+/// This struct is created by [`zip_watch_stream`].
+pub struct ZipWatchStream<S, C>
+where
+    S: Stream,
+{
+    stream: Peekable<S>,
+    // An item waiting for the stream to show whether another item follows.
+    pending: Option<S::Item>,
+    receiver: watch::Receiver<C>,
+    value: Option<C>,
+}
+
+impl<S, C> Stream for ZipWatchStream<S, C>
+where
+    S: Stream + Unpin,
+    S::Item: Unpin,
+    C: Clone + Unpin,
+{
+    type Item = (S::Item, C);
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if this.value.is_none() {
+            return Poll::Ready(None);
+        }
+        if this.pending.is_none() {
+            match ready!(Pin::new(&mut this.stream).poll_next(cx)) {
+                Some(item) => this.pending = Some(item),
+                None => {
+                    this.value = None;
+                    return Poll::Ready(None);
+                }
+            }
+        }
+        let more = ready!(Pin::new(&mut this.stream).poll_peek(cx)).is_some();
+        // This also sees an update sent just before the sender was dropped.
+        let latest = this.receiver.borrow_and_update();
+        if latest.has_changed() {
+            this.value = Some(latest.clone());
+        }
+        drop(latest);
+        let value = if more {
+            this.value.clone()
+        } else {
+            this.value.take()
+        };
+        Poll::Ready(this.pending.take().zip(value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.value.is_none() {
+            return (0, Some(0));
+        }
+        let pending = usize::from(self.pending.is_some());
+        let (lower, upper) = self.stream.size_hint();
+        (
+            lower.saturating_add(pending),
+            upper.and_then(|upper| upper.checked_add(pending)),
+        )
+    }
+}
+
+impl<S, C> FusedStream for ZipWatchStream<S, C>
+where
+    S: Stream + Unpin,
+    S::Item: Unpin,
+    C: Clone + Unpin,
+{
+    fn is_terminated(&self) -> bool {
+        self.value.is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+    use std::pin::Pin;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::task::{Context, Poll};
+
+    use ::futures::executor::block_on;
+    use ::futures::stream::{self, StreamExt};
+    use ::tokio::io::AsyncWrite;
+    use ::tokio::sync::watch;
+
+    use super::{write_all_to_each, zip_watch_stream};
+
+    #[derive(Debug)]
+    struct Payload<'a> {
+        count: &'a AtomicU32,
+    }
+
+    impl AsRef<[u8]> for Payload<'_> {
+        fn as_ref(&self) -> &[u8] {
+            b"Hello"
+        }
+    }
+
+    impl Clone for Payload<'_> {
+        fn clone(&self) -> Self {
+            self.count.fetch_add(1, Ordering::Relaxed);
+            Payload { count: self.count }
+        }
+    }
+
+    // A sink that fails every write.
+    struct Broken;
+
+    impl AsyncWrite for Broken {
+        fn poll_write(
+            self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+            _buf: &[u8],
+        ) -> Poll<io::Result<usize>> {
+            Poll::Ready(Err(io::ErrorKind::BrokenPipe.into()))
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    #[test]
+    fn test_write_all_to_each() {
+        let count = AtomicU32::new(0);
+        let mut sinks = vec![Vec::new(); 4];
+        let results = block_on(write_all_to_each(&mut sinks, Payload { count: &count }));
+        assert!(results.iter().all(|result| result.is_ok()));
+        assert!(sinks.iter().all(|sink| sink == b"Hello"));
+        assert_eq!(count.load(Ordering::Relaxed), 3);
+    }
+
+    #[test]
+    fn test_write_all_to_each_error() {
+        let mut sinks = [Broken, Broken];
+        let results = block_on(write_all_to_each(&mut sinks, b"Hello".to_vec()));
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|result| result.is_err()));
+    }
+
+    #[test]
+    fn test_zip_watch_stream() {
+        let count = AtomicU32::new(0);
+        let (sender, receiver) = watch::channel(Payload { count: &count });
+        let mut stream = zip_watch_stream(stream::iter(0..4), receiver);
+        assert_eq!(count.load(Ordering::Relaxed), 1);
+        assert!(block_on(stream.next()).is_some());
+        sender.send_replace(Payload { count: &count });
+        drop(sender);
+        assert_eq!(block_on(stream.count()), 3);
+        // One clone for the initial copy, one for the update, and one for
+        // each item except the last.
+        assert_eq!(count.load(Ordering::Relaxed), 5);
+    }
+}
@@ -0,0 +1,81 @@
+/// An operation recorded by [`ZipCloneIter::trace`](crate::ZipCloneIter::trace).
+///
+/// Method calls are recorded when they are made, followed by the clones and
+/// moves of the value that they cause. Methods that consume the iterator, such
+/// as `count` and `last`, are not recorded, since the trace cannot be read
+/// afterwards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Operation {
+    /// A call to `next`.
+    Next,
+    /// A call to `next_back`.
+    NextBack,
+    /// A call to `nth` with the given index.
+    Nth(usize),
+    /// A call to `nth_back` with the given index.
+    NthBack(usize),
+    /// A call to `find`.
+    Find,
+    /// A call to `try_find`.
+    TryFind,
+    /// A call to `rearm`, installing a new original value.
+    Rearm,
+    /// The value was cloned to keep for the next item.
+    Cloned,
+    /// The held value was moved into the last item. `original` is `true` if
+    /// it was the value passed in rather than a clone.
+    Moved {
+        /// Whether the moved value was the original.
+        original: bool,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Operation::{Cloned, Find, Moved, Next, NextBack, Nth, Rearm};
+    use crate::ZipClone;
+
+    #[test]
+    #[cfg_attr(feature = "predictable", ignore)]
+    fn test_trace() {
+        let mut iter = (0..6).zip_clone(String::from("Hello"));
+        iter.next();
+        iter.nth(1);
+        iter.find(|(i, _)| *i == 4);
+        iter.next_back();
+        assert_eq!(
+            iter.trace(),
+            [
+                Next,
+                Cloned,
+                Nth(1),
+                Cloned,
+                Find,
+                Cloned,
+                NextBack,
+                Moved { original: false }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_trace_rearm() {
+        let mut iter = (0..1).zip_clone(1);
+        iter.next();
+        iter.rearm(2);
+        iter.next();
+        assert_eq!(iter.trace(), [Next, Moved { original: true }, Rearm, Next]);
+    }
+
+    #[test]
+    #[cfg(all(feature = "nightly", not(feature = "predictable")))]
+    fn test_trace_try_fold() {
+        let mut iter = (0..4).zip_clone(String::from("Hello"));
+        iter.next();
+        assert_eq!(iter.try_fold(0, |n, _| Some(n + 1)), Some(3));
+        assert_eq!(
+            iter.trace(),
+            [Next, Cloned, Cloned, Cloned, Moved { original: false }]
+        );
+    }
+}
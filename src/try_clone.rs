@@ -0,0 +1,242 @@
+use alloc::collections::{TryReserveError, VecDeque};
+use alloc::rc::Rc;
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::collections::{HashMap, HashSet};
+#[cfg(feature = "std")]
+use std::hash::{BuildHasher, Hash};
+
+use crate::{zip_duplicate, ByTryClone, ZipCloneIter};
+
+/// Clone a value, returning an error instead of aborting if memory cannot be
+/// allocated.
+///
+/// The implementations for standard collections reserve memory for the clone
+/// using `try_reserve`, then clone the elements into it. Elements are cloned
+/// using [`Clone`], so memory allocated by the elements themselves is not
+/// covered.
+///
+/// Structs and enums whose fields all implement this trait can derive it with
+/// `#[derive(TryClone)]` when the `macros` feature is enabled.
+///
+/// Example:
+/// ```rust
+/// use zip_clone::TryClone;
+///
+/// let v = vec![1, 2, 3];
+/// assert_eq!(v.try_clone(), Ok(vec![1, 2, 3]));
+/// ```
+pub trait TryClone: Sized {
+    /// Return a copy of the value, or an error if memory cannot be allocated.
+    fn try_clone(&self) -> Result<Self, TryReserveError>;
+}
+
+// Types that do not allocate clone infallibly.
+macro_rules! impl_try_clone_copy {
+    ($($t:ty),*) => {
+        $(
+            impl TryClone for $t {
+                fn try_clone(&self) -> Result<Self, TryReserveError> {
+                    Ok(*self)
+                }
+            }
+        )*
+    };
+}
+
+impl_try_clone_copy!(
+    (),
+    bool,
+    char,
+    f32,
+    f64,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    &'static str
+);
+
+impl<T> TryClone for Option<T>
+where
+    T: TryClone,
+{
+    fn try_clone(&self) -> Result<Self, TryReserveError> {
+        match self {
+            Some(value) => Ok(Some(value.try_clone()?)),
+            None => Ok(None),
+        }
+    }
+}
+
+// `Box` is not implemented, since there is no stable way to allocate a box
+// without aborting on failure.
+
+// Cloning a reference-counted pointer does not allocate.
+impl<T> TryClone for Rc<T> {
+    fn try_clone(&self) -> Result<Self, TryReserveError> {
+        Ok(Rc::clone(self))
+    }
+}
+
+impl<T> TryClone for Arc<T> {
+    fn try_clone(&self) -> Result<Self, TryReserveError> {
+        Ok(Arc::clone(self))
+    }
+}
+
+impl<T> TryClone for Vec<T>
+where
+    T: Clone,
+{
+    fn try_clone(&self) -> Result<Self, TryReserveError> {
+        let mut v = Vec::new();
+        v.try_reserve_exact(self.len())?;
+        v.extend_from_slice(self);
+        Ok(v)
+    }
+}
+
+impl<T> TryClone for VecDeque<T>
+where
+    T: Clone,
+{
+    fn try_clone(&self) -> Result<Self, TryReserveError> {
+        let mut v = VecDeque::new();
+        v.try_reserve_exact(self.len())?;
+        v.extend(self.iter().cloned());
+        Ok(v)
+    }
+}
+
+impl TryClone for String {
+    fn try_clone(&self) -> Result<Self, TryReserveError> {
+        let mut s = String::new();
+        s.try_reserve_exact(self.len())?;
+        s.push_str(self);
+        Ok(s)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V, S> TryClone for HashMap<K, V, S>
+where
+    K: Clone + Eq + Hash,
+    V: Clone,
+    S: BuildHasher + Clone,
+{
+    fn try_clone(&self) -> Result<Self, TryReserveError> {
+        let mut map = HashMap::with_hasher(self.hasher().clone());
+        map.try_reserve(self.len())?;
+        map.extend(self.iter().map(|(k, v)| (k.clone(), v.clone())));
+        Ok(map)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, S> TryClone for HashSet<T, S>
+where
+    T: Clone + Eq + Hash,
+    S: BuildHasher + Clone,
+{
+    fn try_clone(&self) -> Result<Self, TryReserveError> {
+        let mut set = HashSet::with_hasher(self.hasher().clone());
+        set.try_reserve(self.len())?;
+        set.extend(self.iter().cloned());
+        Ok(set)
+    }
+}
+
+/// Zip an iterator to a repeatedly cloned value, where cloning may fail.
+///
+/// This is [`zip_clone`](crate::zip_clone) for values that implement
+/// [`TryClone`], using the [`ByTryClone`] strategy. Each item is paired with
+/// the result of cloning the value. As with `zip_clone`, the first item
+/// receives the original and a clone is kept for the following items. A
+/// failed clone gives the error to that item and keeps the value, so later
+/// items can still receive it.
+///
+/// Example:
+/// ```rust
+/// use zip_clone::try_zip_clone;
+///
+/// for (_, v) in try_zip_clone(0..3, vec![0u8; 1024]) {
+///     let v = v.expect("out of memory");
+///     assert_eq!(v.len(), 1024);
+/// }
+/// ```
+pub fn try_zip_clone<I, C>(iter: I, value: C) -> TryZipClone<I, C>
+where
+    I: Iterator,
+    C: TryClone,
+{
+    zip_duplicate(iter, Ok(value), ByTryClone)
+}
+
+/// An iterator that pairs items with the results of cloning a value.
+///
+/// This type is returned by [`try_zip_clone`].
+pub type TryZipClone<I, C> = ZipCloneIter<I, Result<C, TryReserveError>, ByTryClone>;
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{TryReserveError, VecDeque};
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use crate::tests::Clonable;
+    use crate::{try_zip_clone, TryClone};
+
+    // A value that fails every clone.
+    struct Unclonable;
+
+    impl TryClone for Unclonable {
+        fn try_clone(&self) -> Result<Self, TryReserveError> {
+            Err(Vec::<u8>::new().try_reserve(usize::MAX).unwrap_err())
+        }
+    }
+
+    #[test]
+    fn test_try_clone_collections() {
+        let deque = VecDeque::from(vec![1, 2, 3]);
+        assert_eq!(deque.try_clone(), Ok(deque));
+        assert_eq!(String::from("abc").try_clone().as_deref(), Ok("abc"));
+    }
+
+    #[test]
+    fn test_try_zip_clone() {
+        let count = AtomicU32::new(0);
+        let v = vec![Clonable { count: &count }; 2];
+        assert_eq!(count.load(Ordering::Relaxed), 1);
+        let results = try_zip_clone(0..5, v).collect::<Vec<_>>();
+        assert!(results.iter().all(|(_, v)| v.is_ok()));
+        assert_eq!(count.load(Ordering::Relaxed), 9);
+    }
+
+    #[test]
+    fn test_try_zip_clone_original_first() {
+        let value = String::from("Hello");
+        let ptr = value.as_ptr();
+        let results = try_zip_clone(0..2, value).collect::<Vec<_>>();
+        assert_eq!(results[0].1.as_ref().unwrap().as_ptr(), ptr);
+        assert_ne!(results[1].1.as_ref().unwrap().as_ptr(), ptr);
+    }
+
+    #[test]
+    fn test_try_zip_clone_error() {
+        let mut iter = try_zip_clone(0..3, Unclonable);
+        assert!(iter.next().unwrap().1.is_err());
+        assert!(iter.next().unwrap().1.is_err());
+        assert!(iter.next().unwrap().1.is_ok());
+        assert!(iter.next().is_none());
+    }
+}
@@ -0,0 +1,125 @@
+use core::iter::{FusedIterator, Peekable};
+
+use crate::error::RecoverableError;
+
+/// Map items with a fallible function, pairing each result with clones of a
+/// value, and stop at the first error.
+///
+/// The function receives each item and a reference to the value. The last
+/// item receives the original value. If the function returns an error, the
+/// error is returned in a [`RecoverableError`] together with the value that
+/// the item would have received, and iteration ends. The value is the
+/// original if no clone has been made yet, so it can be reused, for example
+/// to retry.
+///
+/// Example:
+/// ```rust
+/// use zip_clone::try_map_clone;
+///
+/// let template = String::from("item {}");
+/// let mut iter = try_map_clone(vec!["1", "x", "3"].into_iter(), template, |word, t| {
+///     word.parse::<u32>().map(|n| t.replace("{}", &n.to_string()))
+/// });
+/// assert_eq!(iter.next().unwrap().unwrap().0, "item 1");
+/// let template = iter.next().unwrap().unwrap_err().into_value();
+/// assert_eq!(template, "item {}");
+/// assert!(iter.next().is_none());
+/// ```
+pub fn try_map_clone<I, C, R, E, F>(iter: I, value: C, f: F) -> TryMapClone<I, C, F>
+where
+    I: Iterator,
+    C: Clone,
+    F: FnMut(I::Item, &C) -> Result<R, E>,
+{
+    TryMapClone {
+        iter: iter.peekable(),
+        value: Some(value),
+        f,
+    }
+}
+
+/// An iterator that maps items with a fallible function, pairing each result
+/// with clones of a value, until the first error.
+///
+/// This struct is created by [`try_map_clone`].
+pub struct TryMapClone<I, C, F>
+where
+    I: Iterator,
+{
+    iter: Peekable<I>,
+    value: Option<C>,
+    f: F,
+}
+
+impl<I, C, R, E, F> Iterator for TryMapClone<I, C, F>
+where
+    I: Iterator,
+    C: Clone,
+    F: FnMut(I::Item, &C) -> Result<R, E>,
+{
+    type Item = Result<(R, C), RecoverableError<E, C>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let value = self.value.take()?;
+        let item = self.iter.next()?;
+        match (self.f)(item, &value) {
+            Ok(mapped) => {
+                if self.iter.peek().is_some() {
+                    self.value = Some(value.clone());
+                }
+                Some(Ok((mapped, value)))
+            }
+            Err(e) => Some(Err(RecoverableError::new(e, value))),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.value.is_none() {
+            return (0, Some(0));
+        }
+        // An error ends iteration, so only one item is certain.
+        let (lower, upper) = self.iter.size_hint();
+        (lower.min(1), upper)
+    }
+}
+
+impl<I, C, R, E, F> FusedIterator for TryMapClone<I, C, F>
+where
+    I: Iterator,
+    C: Clone,
+    F: FnMut(I::Item, &C) -> Result<R, E>,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use crate::tests::Clonable;
+    use crate::try_map_clone;
+
+    #[test]
+    fn test_try_map_clone() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let iter = try_map_clone(1..5, cloned, |i, _| Ok::<_, ()>(i * 2));
+        let mapped = iter.map(|result| result.map(|(i, _)| i).ok());
+        assert_eq!(
+            mapped.collect::<Vec<_>>(),
+            [Some(2), Some(4), Some(6), Some(8)]
+        );
+        assert_eq!(count.load(Ordering::Relaxed), 3);
+    }
+
+    #[test]
+    fn test_try_map_clone_error() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let mut iter = try_map_clone(1..5, cloned, |i, _| if i == 1 { Err(i) } else { Ok(i) });
+        let result = iter.next().unwrap();
+        assert_eq!(result.map(|_| ()).unwrap_err().error(), &1);
+        assert!(iter.next().is_none());
+        assert_eq!(iter.size_hint(), (0, Some(0)));
+        assert_eq!(count.load(Ordering::Relaxed), 0);
+    }
+}
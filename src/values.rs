@@ -0,0 +1,122 @@
+use core::iter::FusedIterator;
+
+use crate::ZipCloneIter;
+
+/// An iterator that returns a clone of a value for each item of an iterator,
+/// and the value itself for the last item.
+///
+/// This struct is created by [`ZipCloneIter::into_values`].
+pub struct IntoValues<I, C>
+where
+    I: Iterator,
+{
+    inner: ZipCloneIter<I, C>,
+}
+
+impl<I, C> IntoValues<I, C>
+where
+    I: Iterator,
+{
+    pub(crate) fn new(inner: ZipCloneIter<I, C>) -> Self {
+        IntoValues { inner }
+    }
+}
+
+impl<I, C> Iterator for IntoValues<I, C>
+where
+    I: Iterator,
+    C: Clone,
+{
+    type Item = C;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(_, value)| value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+
+    fn count(self) -> usize
+    where
+        Self: Sized,
+    {
+        self.inner.count()
+    }
+
+    fn last(self) -> Option<Self::Item>
+    where
+        Self: Sized,
+    {
+        self.inner.last().map(|(_, value)| value)
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.inner.nth(n).map(|(_, value)| value)
+    }
+}
+
+impl<I, C> DoubleEndedIterator for IntoValues<I, C>
+where
+    I: DoubleEndedIterator,
+    C: Clone,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|(_, value)| value)
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.inner.nth_back(n).map(|(_, value)| value)
+    }
+}
+
+impl<I, C> ExactSizeIterator for IntoValues<I, C>
+where
+    I: ExactSizeIterator,
+    C: Clone,
+{
+}
+
+impl<I, C> FusedIterator for IntoValues<I, C>
+where
+    I: Iterator,
+    C: Clone,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use crate::tests::Clonable;
+    use crate::zip_clone;
+
+    #[test]
+    fn test_into_values() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        let values = zip_clone(0..4, cloned).into_values();
+        assert_eq!(values.len(), 4);
+        assert_eq!(values.map(|_| ()).count(), 4);
+        assert_eq!(count.load(Ordering::Relaxed), 3);
+    }
+
+    #[test]
+    fn test_into_values_original_first() {
+        let value = String::from("Hello");
+        let ptr = value.as_ptr();
+        let values = zip_clone(0..3, value).into_values().collect::<Vec<_>>();
+        assert_eq!(values[0].as_ptr(), ptr);
+        assert_ne!(values[1].as_ptr(), ptr);
+        assert_ne!(values[2].as_ptr(), ptr);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "predictable", ignore)]
+    fn test_into_values_last() {
+        let count = AtomicU32::new(0);
+        let cloned = Clonable { count: &count };
+        assert!(zip_clone(0..4, cloned).into_values().last().is_some());
+        assert_eq!(count.load(Ordering::Relaxed), 0);
+    }
+}
@@ -0,0 +1,143 @@
+//! Check that core paths cannot panic, apart from the value's `Clone` impl.
+//!
+//! Each function below calls into the crate with a guard whose destructor
+//! refers to a function that does not exist. If the optimizer cannot remove
+//! the unwinding path through the crate code, linking fails with an error
+//! naming the function that may panic. The check needs optimization, so it
+//! only runs with `cargo test --release`. In a debug build this file compiles
+//! to nothing, and a plain `cargo test` does not check anything.
+//!
+//! Helpers that allocate, such as `grid_of_clones`, are not checked, because
+//! a failed allocation may unwind.
+
+#![cfg(not(debug_assertions))]
+
+use std::ops::Range;
+
+use zip_clone::{
+    fill_from_prototype, fill_none_with_clones, pad_with_clones, replace_matching, zip_clone_exact,
+    ZipClone, ZipCloneIter,
+};
+
+macro_rules! no_panic {
+    ($(fn $name:ident($($arg:ident: $ty:ty),*) -> $ret:ty $body:block)*) => {
+        $(
+            #[inline(never)]
+            fn $name($($arg: $ty),*) -> $ret {
+                struct NoPanic;
+                extern "C" {
+                    #[link_name = concat!("\n\nERROR: `", stringify!($name), "` may panic\n\n")]
+                    fn may_panic() -> !;
+                }
+                impl Drop for NoPanic {
+                    fn drop(&mut self) {
+                        unsafe { may_panic() }
+                    }
+                }
+                let guard = NoPanic;
+                let result = $body;
+                std::mem::forget(guard);
+                result
+            }
+        )*
+    };
+}
+
+type Iter = ZipCloneIter<Range<u32>, u64>;
+
+no_panic! {
+    fn next(iter: &mut Iter) -> Option<(u32, u64)> {
+        iter.next()
+    }
+
+    fn next_back(iter: &mut Iter) -> Option<(u32, u64)> {
+        iter.next_back()
+    }
+
+    fn nth(iter: &mut Iter, n: usize) -> Option<(u32, u64)> {
+        iter.nth(n)
+    }
+
+    fn nth_back(iter: &mut Iter, n: usize) -> Option<(u32, u64)> {
+        iter.nth_back(n)
+    }
+
+    fn count(iter: Iter) -> usize {
+        iter.count()
+    }
+
+    fn last(iter: Iter) -> Option<(u32, u64)> {
+        iter.last()
+    }
+
+    fn find(iter: &mut Iter, target: u32) -> Option<(u32, u64)> {
+        iter.find(|(item, _)| *item == target)
+    }
+
+    fn fold(iter: Iter) -> u64 {
+        iter.fold(0u64, |total, (item, value)| {
+            total.wrapping_add(u64::from(item)).wrapping_add(value)
+        })
+    }
+
+    fn rfold(iter: Iter) -> u64 {
+        iter.rfold(0u64, |total, (item, value)| {
+            total.wrapping_mul(10).wrapping_add(u64::from(item)).wrapping_add(value)
+        })
+    }
+
+    fn for_each(iter: Iter, slots: &mut [u64; 10]) -> () {
+        iter.for_each(|(item, value)| {
+            if let Some(slot) = slots.get_mut(item as usize) {
+                *slot = value;
+            }
+        })
+    }
+
+    fn predicted_clones(iter: &Iter) -> (usize, Option<usize>) {
+        iter.predicted_clones()
+    }
+
+    fn fill(slots: &mut [u64], value: u64) -> usize {
+        fill_from_prototype(slots, value)
+    }
+
+    fn fill_none(slots: &mut [Option<u64>], value: u64) -> usize {
+        fill_none_with_clones(slots, value)
+    }
+
+    fn replace(slice: &mut [u64], value: u64) -> usize {
+        replace_matching(slice, |item| *item == 0, value)
+    }
+
+    fn exact(items: [u32; 4], value: u64) -> [(u32, u64); 4] {
+        zip_clone_exact(items, value)
+    }
+
+    fn pad(iter: Range<u64>, min_len: usize) -> u64 {
+        pad_with_clones(iter, min_len, 7).fold(0u64, |total, item| total.wrapping_add(item))
+    }
+}
+
+#[test]
+fn test_no_panic() {
+    let mut iter = (0..10).zip_clone(5);
+    assert_eq!(next(&mut iter), Some((0, 5)));
+    assert_eq!(next_back(&mut iter), Some((9, 5)));
+    assert_eq!(nth(&mut iter, 1), Some((2, 5)));
+    assert_eq!(nth_back(&mut iter, 1), Some((7, 5)));
+    assert_eq!(find(&mut iter, 4), Some((4, 5)));
+    assert_eq!(predicted_clones(&iter), (1, Some(1)));
+    assert_eq!(count((0..10).zip_clone(5)), 10);
+    assert_eq!(last((0..10).zip_clone(5)), Some((9, 5)));
+    assert_eq!(fold((0..4).zip_clone(5)), 26);
+    assert_eq!(rfold((0..3).zip_clone(1)), 321);
+    let mut slots = [0; 10];
+    for_each((0..10).zip_clone(5), &mut slots);
+    assert_eq!(slots, [5; 10]);
+    assert_eq!(fill(&mut [0; 4], 1), 4);
+    assert_eq!(fill_none(&mut [None, Some(1)], 2), 1);
+    assert_eq!(replace(&mut [0, 1, 0], 2), 2);
+    assert_eq!(exact([1, 2, 3, 4], 5)[3], (4, 5));
+    assert_eq!(pad(0..2, 4), 15);
+}
@@ -0,0 +1,398 @@
+//! Attribute macro to rewrite loops that clone a value on every iteration
+//! into loops using [`zip_clone`](https://docs.rs/zip_clone), and derive
+//! macros for `zip_clone::DistributeOwnership` and `zip_clone::TryClone`.
+//!
+//! This crate is re-exported by `zip_clone` when its `macros` feature is
+//! enabled.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2, TokenTree};
+use quote::{quote, ToTokens};
+use syn::visit_mut::{self, VisitMut};
+use syn::{
+    parse_macro_input, parse_quote, Block, Data, DeriveInput, Expr, ExprForLoop, Fields, FnArg,
+    Ident, Index, ItemFn, Local, Member, Pat, Stmt,
+};
+
+/// Rewrite simple loops in a function that clone a value on every iteration
+/// to use `zip_clone`, so the last iteration moves the value instead.
+///
+/// A loop is rewritten when the first statement of its body is
+/// `let PAT = v.clone();` or `y = v.clone();`, where `v` is a local variable
+/// or parameter declared in the block containing the loop, and `v` is not
+/// mentioned anywhere else in the loop, unless the `let` shadows it, or after
+/// the loop in that block. Other loops are left unchanged.
+///
+/// `zip_clone` takes each item from the iterator before running the loop body
+/// for the previous item, to know whether it is the last. This changes the
+/// order of side effects of an iterator that has them, such as one reading
+/// lines of input. To keep the order, a loop is only rewritten if it iterates
+/// over a range, such as `0..n`, a shared reference, such as `&v`, or the
+/// `iter()` or `iter_mut()` of a slice or `Vec`.
+///
+/// The value must be owned. If `v` is a reference, the rewritten loop zips
+/// the reference instead of cloning the value it refers to, and fails to
+/// compile.
+///
+/// Example:
+/// ```rust
+/// use zip_clone_macros::minimize_clones;
+///
+/// #[minimize_clones]
+/// fn greetings(n: usize) -> Vec<String> {
+///     let mut v = Vec::new();
+///     let s = String::from("Hello");
+///     for _ in 0..n {
+///         let s = s.clone();
+///         v.push(s);
+///     }
+///     v
+/// }
+/// # assert_eq!(greetings(3), ["Hello", "Hello", "Hello"]);
+/// ```
+/// expands to a loop that clones `s` two times rather than three:
+/// ```rust
+/// # fn greetings(n: usize) -> Vec<String> {
+/// #     let mut v = Vec::new();
+/// #     let s = String::from("Hello");
+/// for (_, value) in ::zip_clone::ZipClone::zip_clone(0..n, s) {
+///     let s = value;
+///     v.push(s);
+/// }
+/// #     v
+/// # }
+/// ```
+#[proc_macro_attribute]
+pub fn minimize_clones(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let attr = TokenStream2::from(attr);
+    if !attr.is_empty() {
+        return syn::Error::new_spanned(attr, "`minimize_clones` takes no arguments")
+            .to_compile_error()
+            .into();
+    }
+    let mut item = parse_macro_input!(item as ItemFn);
+    let params = item
+        .sig
+        .inputs
+        .iter()
+        .filter_map(|arg| match arg {
+            FnArg::Typed(arg) => pat_ident(&arg.pat),
+            FnArg::Receiver(_) => None,
+        })
+        .collect();
+    let mut rewriter = Rewriter { params };
+    rewriter.visit_block_mut(&mut item.block);
+    item.into_token_stream().into()
+}
+
+struct Rewriter {
+    // The parameters of the function, which are declared in its outer block.
+    params: Vec<Ident>,
+}
+
+impl VisitMut for Rewriter {
+    fn visit_block_mut(&mut self, block: &mut Block) {
+        // The function parameters are only in scope for its outer block.
+        let mut declared = std::mem::take(&mut self.params);
+        visit_mut::visit_block_mut(self, block);
+        for index in 0..block.stmts.len() {
+            let (stmt, later) = block.stmts[index..].split_first_mut().unwrap();
+            match stmt {
+                Stmt::Local(local) => declared.extend(pat_ident(&local.pat)),
+                Stmt::Expr(Expr::ForLoop(for_loop), _) => {
+                    rewrite_loop(for_loop, &declared, later);
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+// Rewrite a loop to use `zip_clone` if its first statement clones one of the
+// `declared` variables and the variable is not used again.
+fn rewrite_loop(for_loop: &mut ExprForLoop, declared: &[Ident], later: &[Stmt]) {
+    let value = match for_loop.body.stmts.first().and_then(cloned_variable) {
+        Some(value) => value,
+        None => return,
+    };
+    // A `let` that rebinds the name shadows the variable for the rest of the
+    // loop body, as in `let s = s.clone();`.
+    let shadowed = match &for_loop.body.stmts[0] {
+        Stmt::Local(local) => mentions(&local.pat, &value),
+        _ => false,
+    };
+    if !declared.contains(&value)
+        || !side_effect_free(&for_loop.expr)
+        || mentions(&for_loop.expr, &value)
+        || (!shadowed
+            && for_loop.body.stmts[1..]
+                .iter()
+                .any(|stmt| mentions(stmt, &value)))
+        || later.iter().any(|stmt| mentions(stmt, &value))
+    {
+        return;
+    }
+    let clone = Ident::new("__zip_clone_value", Span::mixed_site());
+    match &mut for_loop.body.stmts[0] {
+        Stmt::Local(local) => {
+            if let Some(init) = &mut local.init {
+                *init.expr = parse_quote!(#clone);
+            }
+        }
+        Stmt::Expr(Expr::Assign(assign), _) => {
+            if mentions(&assign.left, &value) {
+                return;
+            }
+            *assign.right = parse_quote!(#clone);
+        }
+        _ => unreachable!(),
+    }
+    let pat = &for_loop.pat;
+    let expr = &for_loop.expr;
+    let (pat, expr) = (
+        parse_quote!((#pat, #clone)),
+        parse_quote!(
+            ::zip_clone::ZipClone::zip_clone(::core::iter::IntoIterator::into_iter(#expr), #value)
+        ),
+    );
+    *for_loop.pat = pat;
+    *for_loop.expr = expr;
+}
+
+// Return `true` if taking items from the iterator of an expression has no
+// side effects, so taking each item early does not change the behavior of
+// the loop. The expression itself is evaluated once, before the loop, whether
+// the loop is rewritten or not.
+fn side_effect_free(expr: &Expr) -> bool {
+    match expr {
+        Expr::Paren(paren) => side_effect_free(&paren.expr),
+        Expr::Range(_) => true,
+        // `&mut it` may be an iterator with side effects.
+        Expr::Reference(reference) => reference.mutability.is_none(),
+        Expr::MethodCall(call) => {
+            (call.method == "iter" || call.method == "iter_mut")
+                && call.args.is_empty()
+                && call.turbofish.is_none()
+        }
+        _ => false,
+    }
+}
+
+// Return the variable cloned by a statement of the form `let PAT = v.clone();`
+// or `y = v.clone();`.
+fn cloned_variable(stmt: &Stmt) -> Option<Ident> {
+    let expr = match stmt {
+        Stmt::Local(Local {
+            init: Some(init), ..
+        }) if init.diverge.is_none() => &init.expr,
+        Stmt::Expr(Expr::Assign(assign), Some(_)) => &assign.right,
+        _ => return None,
+    };
+    match &**expr {
+        Expr::MethodCall(call)
+            if call.method == "clone" && call.args.is_empty() && call.turbofish.is_none() =>
+        {
+            match &*call.receiver {
+                Expr::Path(path) if path.qself.is_none() => path.path.get_ident().cloned(),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+// Return the variable bound by a pattern consisting of a single identifier.
+fn pat_ident(pat: &Pat) -> Option<Ident> {
+    match pat {
+        Pat::Ident(pat) if pat.subpat.is_none() => Some(pat.ident.clone()),
+        Pat::Type(pat) => pat_ident(&pat.pat),
+        _ => None,
+    }
+}
+
+// Return `true` if the identifier appears anywhere in the tokens, including
+// inside macro invocations and shadowing declarations.
+fn mentions<T: ToTokens>(tokens: &T, ident: &Ident) -> bool {
+    fn search(tokens: TokenStream2, ident: &Ident) -> bool {
+        tokens.into_iter().any(|tree| match tree {
+            TokenTree::Ident(i) => i == *ident,
+            TokenTree::Group(group) => search(group.stream(), ident),
+            _ => false,
+        })
+    }
+    search(quote!(#tokens), ident)
+}
+
+/// Derive `zip_clone::DistributeOwnership` for a struct whose fields all
+/// implement it.
+///
+/// Distributing the struct distributes each field, and combines the shares
+/// into a new struct.
+///
+/// Example:
+/// ```rust
+/// use std::sync::Arc;
+/// use zip_clone::DistributeOwnership as _;
+/// use zip_clone_macros::DistributeOwnership;
+///
+/// #[derive(DistributeOwnership)]
+/// struct Context {
+///     config: Arc<String>,
+///     buffer: Option<Arc<Vec<u8>>>,
+/// }
+///
+/// let mut context = Context {
+///     config: Arc::new(String::from("verbose")),
+///     buffer: None,
+/// };
+/// let share = context.distribute();
+/// assert!(Arc::ptr_eq(&share.config, &context.config));
+/// ```
+#[proc_macro_derive(DistributeOwnership)]
+pub fn derive_distribute_ownership(item: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(item as DeriveInput);
+    let data = match &input.data {
+        Data::Struct(data) => data,
+        _ => {
+            return syn::Error::new_spanned(
+                &input.ident,
+                "`DistributeOwnership` can only be derived for structs",
+            )
+            .to_compile_error()
+            .into();
+        }
+    };
+    let name = &input.ident;
+    let mut generics = input.generics.clone();
+    let where_clause = generics.make_where_clause();
+    for field in &data.fields {
+        let ty = &field.ty;
+        where_clause
+            .predicates
+            .push(parse_quote!(#ty: ::zip_clone::DistributeOwnership));
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let fields = data.fields.iter().enumerate().map(|(index, field)| {
+        let member = match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(Index::from(index)),
+        };
+        quote! {
+            #member: ::zip_clone::DistributeOwnership::distribute(&mut self.#member)
+        }
+    });
+    quote! {
+        impl #impl_generics ::zip_clone::DistributeOwnership for #name #ty_generics #where_clause {
+            fn distribute(&mut self) -> Self {
+                #name { #(#fields,)* }
+            }
+        }
+    }
+    .into()
+}
+
+/// Derive `zip_clone::TryClone` for a struct or enum whose fields all
+/// implement it.
+///
+/// Each field is cloned with `try_clone`, and the first error is returned.
+///
+/// Example:
+/// ```rust
+/// use zip_clone::TryClone as _;
+/// use zip_clone_macros::TryClone;
+///
+/// #[derive(Debug, PartialEq, TryClone)]
+/// struct Record {
+///     id: u64,
+///     name: String,
+///     tags: Vec<String>,
+/// }
+///
+/// let record = Record {
+///     id: 1,
+///     name: String::from("first"),
+///     tags: vec![String::from("new")],
+/// };
+/// assert_eq!(record.try_clone(), Ok(record));
+/// ```
+#[proc_macro_derive(TryClone)]
+pub fn derive_try_clone(item: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(item as DeriveInput);
+    let name = &input.ident;
+    let mut generics = input.generics.clone();
+    let where_clause = generics.make_where_clause();
+    let field_types: Vec<_> = match &input.data {
+        Data::Struct(data) => data.fields.iter().map(|field| &field.ty).collect(),
+        Data::Enum(data) => data
+            .variants
+            .iter()
+            .flat_map(|variant| variant.fields.iter().map(|field| &field.ty))
+            .collect(),
+        Data::Union(_) => {
+            return syn::Error::new_spanned(name, "`TryClone` cannot be derived for unions")
+                .to_compile_error()
+                .into();
+        }
+    };
+    for ty in field_types {
+        where_clause
+            .predicates
+            .push(parse_quote!(#ty: ::zip_clone::TryClone));
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let (pattern, construct) = try_clone_fields(quote!(#name), &data.fields);
+            quote! {
+                let #pattern = self;
+                ::core::result::Result::Ok(#construct)
+            }
+        }
+        Data::Enum(data) => {
+            let arms = data.variants.iter().map(|variant| {
+                let variant_name = &variant.ident;
+                let (pattern, construct) =
+                    try_clone_fields(quote!(#name::#variant_name), &variant.fields);
+                quote!(#pattern => #construct,)
+            });
+            quote! {
+                ::core::result::Result::Ok(match self {
+                    #(#arms)*
+                })
+            }
+        }
+        Data::Union(_) => unreachable!(),
+    };
+    quote! {
+        impl #impl_generics ::zip_clone::TryClone for #name #ty_generics #where_clause {
+            fn try_clone(
+                &self,
+            ) -> ::core::result::Result<Self, ::zip_clone::TryReserveError> {
+                #body
+            }
+        }
+    }
+    .into()
+}
+
+// Return a pattern binding references to the fields, and an expression
+// constructing a new value from fallible clones of the bound fields.
+fn try_clone_fields(path: TokenStream2, fields: &Fields) -> (TokenStream2, TokenStream2) {
+    let bindings: Vec<_> = (0..fields.len())
+        .map(|index| Ident::new(&format!("__field{}", index), Span::mixed_site()))
+        .collect();
+    let clones = bindings
+        .iter()
+        .map(|binding| quote!(::zip_clone::TryClone::try_clone(#binding)?));
+    match fields {
+        Fields::Named(named) => {
+            let names: Vec<_> = named.named.iter().map(|field| &field.ident).collect();
+            (
+                quote!(#path { #(#names: #bindings),* }),
+                quote!(#path { #(#names: #clones),* }),
+            )
+        }
+        Fields::Unnamed(_) => (quote!(#path(#(#bindings),*)), quote!(#path(#(#clones),*))),
+        Fields::Unit => (path.clone(), path),
+    }
+}
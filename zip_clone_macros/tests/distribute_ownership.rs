@@ -0,0 +1,39 @@
+use std::marker::PhantomData;
+use std::rc::Rc;
+
+use zip_clone::{zip_duplicate, ByDistribute, DistributeOwnership as _};
+use zip_clone_macros::DistributeOwnership;
+
+#[derive(DistributeOwnership)]
+struct Named<T> {
+    shared: Rc<T>,
+    optional: Option<Rc<String>>,
+    marker: PhantomData<T>,
+}
+
+#[derive(DistributeOwnership)]
+struct Tuple(Rc<u32>, ());
+
+#[derive(DistributeOwnership)]
+struct Unit;
+
+#[test]
+fn test_derive_named() {
+    let mut named = Named {
+        shared: Rc::new(5),
+        optional: Some(Rc::new(String::from("Hello"))),
+        marker: PhantomData,
+    };
+    let share = named.distribute();
+    assert!(Rc::ptr_eq(&share.shared, &named.shared));
+    assert_eq!(Rc::strong_count(named.optional.as_ref().unwrap()), 2);
+}
+
+#[test]
+fn test_derive_tuple() {
+    let shares = zip_duplicate(0..3, Tuple(Rc::new(5), ()), ByDistribute)
+        .map(|(_, share)| share)
+        .collect::<Vec<_>>();
+    assert_eq!(Rc::strong_count(&shares[0].0), 3);
+    let Unit = Unit.distribute();
+}
@@ -0,0 +1,137 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use zip_clone_macros::minimize_clones;
+
+#[derive(Debug)]
+struct Clonable<'a> {
+    count: &'a AtomicU32,
+}
+
+impl Clone for Clonable<'_> {
+    fn clone(&self) -> Self {
+        self.count.fetch_add(1, Ordering::Relaxed);
+        Clonable { count: self.count }
+    }
+}
+
+#[minimize_clones]
+fn collect_let(n: usize, value: Clonable<'_>) -> Vec<Clonable<'_>> {
+    let mut v = Vec::new();
+    for _ in 0..n {
+        let value = value.clone();
+        v.push(value);
+    }
+    v
+}
+
+#[minimize_clones]
+fn collect_assign(n: usize, count: &AtomicU32) -> usize {
+    let value = Clonable { count };
+    let mut found = Clonable { count };
+    let mut total = 0;
+    'outer: for i in 0..n {
+        found = value.clone();
+        total += i;
+        if total > 100 {
+            break 'outer;
+        }
+    }
+    assert!(std::ptr::eq(found.count, count));
+    total
+}
+
+#[minimize_clones]
+fn nested(n: usize, count: &AtomicU32) -> usize {
+    let mut total = 0;
+    for _ in 0..n {
+        let value = Clonable { count };
+        for _ in 0..n {
+            let _value = value.clone();
+            total += 1;
+        }
+    }
+    total
+}
+
+#[minimize_clones]
+fn used_after(n: usize, value: Clonable<'_>) -> Clonable<'_> {
+    for _ in 0..n {
+        let _value = value.clone();
+    }
+    value
+}
+
+#[minimize_clones]
+fn collect_slice<'a>(items: &[u32], value: Clonable<'a>) -> Vec<Clonable<'a>> {
+    let mut v = Vec::new();
+    for _ in items.iter() {
+        let value = value.clone();
+        v.push(value);
+    }
+    v
+}
+
+// The iterator reads the number of clones made, so taking an item early would
+// change what it returns.
+#[minimize_clones]
+fn side_effects(n: u32, count: &AtomicU32) -> Vec<u32> {
+    let value = Clonable { count };
+    let mut seen = Vec::new();
+    for i in (0..n).map(|_| count.load(Ordering::Relaxed)) {
+        let _value = value.clone();
+        seen.push(i);
+    }
+    seen
+}
+
+#[test]
+fn test_minimize_clones_let() {
+    let count = AtomicU32::new(0);
+    assert_eq!(collect_let(5, Clonable { count: &count }).len(), 5);
+    assert_eq!(count.load(Ordering::Relaxed), 4);
+}
+
+#[test]
+fn test_minimize_clones_empty() {
+    let count = AtomicU32::new(0);
+    assert!(collect_let(0, Clonable { count: &count }).is_empty());
+    assert_eq!(count.load(Ordering::Relaxed), 0);
+}
+
+#[test]
+fn test_minimize_clones_assign() {
+    let count = AtomicU32::new(0);
+    assert_eq!(collect_assign(5, &count), 10);
+    assert_eq!(count.load(Ordering::Relaxed), 4);
+}
+
+#[test]
+fn test_minimize_clones_nested() {
+    let count = AtomicU32::new(0);
+    assert_eq!(nested(3, &count), 9);
+    assert_eq!(count.load(Ordering::Relaxed), 6);
+}
+
+#[test]
+fn test_minimize_clones_used_after() {
+    let count = AtomicU32::new(0);
+    used_after(5, Clonable { count: &count });
+    assert_eq!(count.load(Ordering::Relaxed), 5);
+}
+
+#[test]
+fn test_minimize_clones_slice() {
+    let count = AtomicU32::new(0);
+    assert_eq!(
+        collect_slice(&[1, 2, 3], Clonable { count: &count }).len(),
+        3
+    );
+    assert_eq!(count.load(Ordering::Relaxed), 2);
+}
+
+#[test]
+fn test_minimize_clones_side_effects() {
+    let count = AtomicU32::new(0);
+    assert_eq!(side_effects(3, &count), [0, 1, 2]);
+    assert_eq!(count.load(Ordering::Relaxed), 3);
+}
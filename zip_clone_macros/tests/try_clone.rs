@@ -0,0 +1,55 @@
+use std::rc::Rc;
+
+use zip_clone::{try_zip_clone, TryClone as _};
+use zip_clone_macros::TryClone;
+
+#[derive(Debug, PartialEq, TryClone)]
+struct Named<T> {
+    values: Vec<T>,
+    name: Option<String>,
+    shared: Rc<u32>,
+}
+
+#[derive(Debug, PartialEq, TryClone)]
+struct Tuple(String, u8);
+
+#[derive(Debug, PartialEq, TryClone)]
+enum Message {
+    Empty,
+    Text(String),
+    Record { id: u64, body: Vec<u8> },
+}
+
+#[test]
+fn test_derive_struct() {
+    let named = Named {
+        values: vec![1, 2, 3],
+        name: Some(String::from("Hello")),
+        shared: Rc::new(5),
+    };
+    let clone = named.try_clone().unwrap();
+    assert!(Rc::ptr_eq(&clone.shared, &named.shared));
+    assert_eq!(clone, named);
+    let tuple = Tuple(String::from("a"), 1);
+    assert_eq!(tuple.try_clone(), Ok(tuple));
+}
+
+#[test]
+fn test_derive_enum() {
+    for message in [
+        Message::Empty,
+        Message::Text(String::from("Hello")),
+        Message::Record {
+            id: 1,
+            body: vec![1, 2],
+        },
+    ] {
+        assert_eq!(message.try_clone().as_ref(), Ok(&message));
+    }
+}
+
+#[test]
+fn test_derive_try_zip_clone() {
+    let results = try_zip_clone(0..3, Tuple(String::from("a"), 1)).collect::<Vec<_>>();
+    assert!(results.iter().all(|(_, value)| value.is_ok()));
+}